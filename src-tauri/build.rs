@@ -1,3 +1,49 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// SFX 스텁(sfx-stub 크레이트)을 앱과 같은 타깃으로 릴리스 빌드해 gen/sfx-stub/sfx_stub.exe로 복사합니다.
+// tauri.conf.json의 bundle.resources가 이 파일을 리소스 폴더에 넣어 create_sfx가 찾을 수 있게 합니다.
+// tauri_build가 리소스 존재 여부를 검사하므로 tauri_build::build()보다 먼저 실행해야 합니다.
+fn build_sfx_stub() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let stub_dir = manifest_dir.join("sfx-stub");
+    println!("cargo:rerun-if-changed={}", stub_dir.join("src").display());
+    println!(
+        "cargo:rerun-if-changed={}",
+        stub_dir.join("Cargo.toml").display()
+    );
+
+    let target = env::var("TARGET").unwrap();
+    let target_dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("sfx-stub");
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    // 바깥 빌드(clippy 등)의 래퍼와 플래그가 스텁 빌드에 섞이지 않도록 지웁니다.
+    let status = Command::new(cargo)
+        .current_dir(&stub_dir)
+        .args(["build", "--release", "--target", &target, "--target-dir"])
+        .arg(&target_dir)
+        .env_remove("RUSTC_WORKSPACE_WRAPPER")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .status()
+        .expect("failed to run cargo for sfx-stub");
+    assert!(status.success(), "sfx-stub build failed");
+
+    let exe_suffix = if target.contains("windows") {
+        ".exe"
+    } else {
+        ""
+    };
+    let built = target_dir
+        .join(&target)
+        .join("release")
+        .join(format!("mhzipy-sfx-stub{}", exe_suffix));
+    let out_dir = manifest_dir.join("gen").join("sfx-stub");
+    fs::create_dir_all(&out_dir).unwrap();
+    fs::copy(&built, out_dir.join("sfx_stub.exe")).unwrap();
+}
+
 fn main() {
+    build_sfx_stub();
     tauri_build::build()
 }
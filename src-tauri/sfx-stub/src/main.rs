@@ -0,0 +1,108 @@
+// mhzipy 자동 압축 해제(SFX) 스텁
+// 자기 자신의 실행 파일 뒤에 붙은 ZIP을 열어 지정한 폴더에 풀어냅니다. README.md 참고.
+use std::fs::{self, File};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+fn prompt(message: &str) -> io::Result<String> {
+    print!("{}", message);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn default_target(exe: &Path) -> PathBuf {
+    let dir = exe.parent().unwrap_or(Path::new("."));
+    let stem = exe
+        .file_stem()
+        .map(|s| s.to_os_string())
+        .unwrap_or_else(|| "extracted".into());
+    dir.join(stem)
+}
+
+fn run() -> Result<usize, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(File::open(&exe).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("No archive attached to this executable: {}", e))?;
+
+    let target = match std::env::args().nth(1) {
+        Some(arg) => PathBuf::from(arg),
+        None => {
+            let default = default_target(&exe);
+            let input = prompt(&format!("Extract to [{}]: ", default.display()))
+                .map_err(|e| e.to_string())?;
+            if input.trim().is_empty() {
+                default
+            } else {
+                PathBuf::from(input.trim())
+            }
+        }
+    };
+
+    let mut encrypted = false;
+    for i in 0..archive.len() {
+        if archive
+            .by_index_raw(i)
+            .map_err(|e| e.to_string())?
+            .encrypted()
+        {
+            encrypted = true;
+            break;
+        }
+    }
+    let password = if encrypted {
+        Some(prompt("Password: ").map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+
+    fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+    let mut extracted = 0;
+    for i in 0..archive.len() {
+        let is_encrypted = archive
+            .by_index_raw(i)
+            .map_err(|e| e.to_string())?
+            .encrypted();
+        let mut file = match &password {
+            Some(p) if is_encrypted => archive
+                .by_index_decrypt(i, p.as_bytes())
+                .map_err(|e| e.to_string())?,
+            _ => archive.by_index(i).map_err(|e| e.to_string())?,
+        };
+        // 대상 폴더를 벗어나는 경로(../, 절대 경로)는 건너뜀
+        let Some(relative) = file.enclosed_name() else {
+            eprintln!("Skipped unsafe path: {}", file.name());
+            continue;
+        };
+        let outpath = target.join(relative);
+        if file.is_dir() {
+            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = outpath.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = File::create(&outpath).map_err(|e| e.to_string())?;
+        io::copy(&mut file, &mut out).map_err(|e| format!("{}: {}", file.name(), e))?;
+        println!("{}", file.name());
+        extracted += 1;
+    }
+    Ok(extracted)
+}
+
+fn main() {
+    let code = match run() {
+        Ok(count) => {
+            println!("Extracted {} file(s).", count);
+            0
+        }
+        Err(e) => {
+            eprintln!("Extraction failed: {}", e);
+            1
+        }
+    };
+    // 탐색기에서 더블클릭으로 실행한 경우 창이 바로 닫히지 않도록 대기
+    let _ = prompt("Press Enter to close...");
+    std::process::exit(code);
+}
@@ -0,0 +1,95 @@
+use std::io::{self, Read};
+
+// 압축 폭탄 방지 (압축 해제 크기 합계와 압축률 제한)
+//
+// 해제 전에 중앙 디렉터리에 기록된 크기로 한 번 검사하고(check_declared), 기록된 크기는
+// 조작될 수 있으므로 해제하면서 실제로 쓴 바이트도 누적해 검사합니다(add).
+// 압축률은 아카이브 파일 크기 대비 누적 해제 크기로 계산하므로, 여러 항목이 같은 압축 데이터를
+// 가리키는 겹친 항목 폭탄도 잡아냅니다. 0으로 채운 작은 파일처럼 압축률만 높은 정상 파일이
+// 걸리지 않도록 RATIO_MIN_BYTES를 넘은 뒤부터 압축률을 검사합니다.
+// 제한을 넘으면 "ZIP_BOMB: <사유>" 오류를 반환합니다.
+
+// 기본 최대 압축률 (deflate 한 스트림의 이론상 최대치가 약 1032:1)
+pub const DEFAULT_MAX_RATIO: u64 = 1000;
+// 이 크기 이하로 해제된 동안은 압축률을 검사하지 않음
+const RATIO_MIN_BYTES: u64 = 64 * 1024 * 1024;
+
+pub struct BombGuard {
+    archive_size: u64,
+    max_total: Option<u64>,
+    max_ratio: Option<u64>,
+    written: u64,
+}
+
+impl BombGuard {
+    // max_ratio가 None이면 기본값, Some(0)이면 압축률 검사를 하지 않음
+    pub fn new(archive_size: u64, max_total: Option<u64>, max_ratio: Option<u64>) -> Self {
+        Self {
+            archive_size: archive_size.max(1),
+            max_total,
+            max_ratio: match max_ratio {
+                None => Some(DEFAULT_MAX_RATIO),
+                Some(0) => None,
+                Some(ratio) => Some(ratio),
+            },
+            written: 0,
+        }
+    }
+
+    // 기록된 (해제 크기, 압축 크기) 목록을 해제 전에 검사
+    pub fn check_declared(
+        &self,
+        entries: impl IntoIterator<Item = (u64, u64)>,
+    ) -> Result<(), String> {
+        let mut total = 0u64;
+        for (size, compressed) in entries {
+            if let Some(max_ratio) = self.max_ratio {
+                if size > RATIO_MIN_BYTES && size / compressed.max(1) > max_ratio {
+                    return Err(format!("ZIP_BOMB: entry ratio exceeds {}", max_ratio));
+                }
+            }
+            total = total.saturating_add(size);
+        }
+        self.check(total)
+    }
+
+    // 실제로 해제한 바이트를 누적하고 제한을 넘으면 오류
+    pub fn add(&mut self, n: u64) -> Result<(), String> {
+        self.written = self.written.saturating_add(n);
+        self.check(self.written)
+    }
+
+    fn check(&self, total: u64) -> Result<(), String> {
+        if let Some(max_total) = self.max_total {
+            if total > max_total {
+                return Err(format!("ZIP_BOMB: total size exceeds {}", max_total));
+            }
+        }
+        if let Some(max_ratio) = self.max_ratio {
+            if total > RATIO_MIN_BYTES && total / self.archive_size > max_ratio {
+                return Err(format!("ZIP_BOMB: ratio exceeds {}", max_ratio));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 읽은 바이트를 BombGuard에 누적하는 Reader (io::copy와 함께 사용)
+pub struct GuardedReader<'a, R> {
+    inner: R,
+    guard: &'a mut BombGuard,
+}
+
+impl<'a, R: Read> GuardedReader<'a, R> {
+    pub fn new(inner: R, guard: &'a mut BombGuard) -> Self {
+        Self { inner, guard }
+    }
+}
+
+impl<R: Read> Read for GuardedReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.guard.add(n as u64).map_err(io::Error::other)?;
+        Ok(n)
+    }
+}
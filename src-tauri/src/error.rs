@@ -0,0 +1,113 @@
+use std::io;
+
+// 프론트엔드에서 분기할 수 있는 오류 코드 (직렬화 시 "FILE_EXISTS" 형식)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    PasswordRequired,
+    InvalidPassword,
+    PasswordTooShort,
+    FileExists,
+    PathTraversal,
+    Cancelled,
+    NotEncrypted,
+    EntryTooLarge,
+    PrefixMismatch,
+    IndexRebuildRequired,
+    Io,
+    Zip,
+    Unknown,
+}
+
+// 명령어가 반환하는 오류 ({ code, message })
+// 프론트엔드는 code로 분기하고, message는 화면 표시용으로만 사용합니다.
+#[derive(Debug, serde::Serialize)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+// 내부 함수들은 여전히 String 오류를 사용하므로, "FILE_EXISTS", "PATH_TRAVERSAL: <이름>" 같은
+// 약속된 문자열과 zip 크레이트의 비밀번호 오류 문구를 코드로 분류합니다.
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        let (head, detail) = match message.split_once(": ") {
+            Some((head, detail)) => (head, Some(detail)),
+            None => (message.as_str(), None),
+        };
+        let known = match head {
+            "FILE_EXISTS" => Some((ErrorCode::FileExists, "File already exists")),
+            "PASSWORD_TOO_SHORT" => Some((
+                ErrorCode::PasswordTooShort,
+                "Password is shorter than the minimum length",
+            )),
+            "PATH_TRAVERSAL" => Some((
+                ErrorCode::PathTraversal,
+                "Entry path escapes the target folder",
+            )),
+            "CANCELLED" => Some((ErrorCode::Cancelled, "Operation was cancelled")),
+            "NOT_ENCRYPTED" => Some((ErrorCode::NotEncrypted, "Archive is not encrypted")),
+            "ENTRY_TOO_LARGE" => Some((ErrorCode::EntryTooLarge, "Entry is too large to preview")),
+            "PREFIX_MISMATCH" => Some((
+                ErrorCode::PrefixMismatch,
+                "Entry does not start with the prefix",
+            )),
+            "INDEX_REBUILD_REQUIRED" => {
+                Some((ErrorCode::IndexRebuildRequired, "Index must be rebuilt"))
+            }
+            _ => None,
+        };
+        if let Some((code, text)) = known {
+            let message = match detail {
+                Some(detail) => format!("{}: {}", text, detail),
+                None => text.to_string(),
+            };
+            return Self::new(code, message);
+        }
+
+        let lower = message.to_lowercase();
+        let code = if lower.contains("password required") {
+            ErrorCode::PasswordRequired
+        } else if lower.contains("invalid password") {
+            ErrorCode::InvalidPassword
+        } else {
+            ErrorCode::Unknown
+        };
+        Self::new(code, message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<io::Error> for CommandError {
+    fn from(e: io::Error) -> Self {
+        Self::new(ErrorCode::Io, e.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for CommandError {
+    fn from(e: zip::result::ZipError) -> Self {
+        let code = match &e {
+            zip::result::ZipError::InvalidPassword => ErrorCode::InvalidPassword,
+            zip::result::ZipError::Io(_) => ErrorCode::Io,
+            _ if e.to_string().to_lowercase().contains("password required") => {
+                ErrorCode::PasswordRequired
+            }
+            _ => ErrorCode::Zip,
+        };
+        Self::new(code, e.to_string())
+    }
+}
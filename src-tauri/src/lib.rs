@@ -1,19 +1,40 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+use std::collections::BTreeMap;
 use std::fs::{self, File};
-use std::io::{self, BufReader, Read, Write};
-use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::time::{Instant, SystemTime};
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
 use sysinfo::Disks;
-use tauri::{AppHandle, Emitter, Manager, Window};
+use tauri::{AppHandle, Emitter, Manager, Runtime, Window};
 use walkdir::WalkDir;
 use zip::write::FileOptions;
 use zip::unstable::write::FileOptionsExt;
+use rayon::prelude::*;
 use regex::RegexBuilder;
+use sha2::{Digest, Sha256};
 
 mod mft;
-use mft::MftIndex;
+use mft::{
+    search_all, DirChild, IndexState, IndexStats, MftIndex, NameMatcher, SearchFilter, SearchMode,
+    SearchSort, ValidationReport, DEFAULT_MAX_PATH_DEPTH, DEFAULT_REASON_MASK,
+    DEFAULT_SEARCH_EXCLUSIONS, INDEX_REBUILD_REQUIRED,
+};
+mod bomb;
+use bomb::{BombGuard, GuardedReader};
+mod error;
+use error::CommandError;
 mod license;
+mod manifest;
+use manifest::Manifest;
+mod password;
+mod resume;
+use resume::{ArchiveFingerprint, ResumeLog};
+mod sfx;
+mod split;
+use split::SplitWriter;
+mod tarball;
 
 #[derive(serde::Serialize)]
 struct ZipEntry {
@@ -25,11 +46,54 @@ struct ZipEntry {
     is_encrypted: bool,
 }
 
+// ZIP 내용 목록 (항목 + 아카이브 전체 주석)
+#[derive(serde::Serialize)]
+struct ZipContents {
+    entries: Vec<ZipEntry>,
+    comment: Option<String>,
+}
+
 #[derive(Clone, serde::Serialize)]
 struct ProgressPayload {
+    // 전체 크기를 미리 계산하지 않은 진행 상황(indeterminate)이면 0
     total: u64,
     processed: u64,
     filename: String,
+    // 최근 구간 기준 처리 속도 (바이트/초)
+    #[serde(rename = "bytesPerSec")]
+    bytes_per_sec: u64,
+    // 남은 예상 시간 (초), 속도를 아직 알 수 없으면 None
+    #[serde(rename = "etaSecs")]
+    eta_secs: Option<u64>,
+    // 완료 이벤트에서만 결과 파일 경로를 전달
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct IndexProgressPayload {
+    // 인덱싱 중인 드라이브 (예: "D:")
+    drive: String,
+    processed: usize,
+}
+
+// 드라이브별 인덱스 상태 (get_index_state 결과와 "index-state" 이벤트)
+#[derive(Clone, serde::Serialize)]
+struct IndexStatePayload {
+    drive: String,
+    state: IndexState,
+}
+
+// 인덱스 상태를 바꾸고 "index-state" 이벤트로 알림
+fn set_index_state(app: &AppHandle, index: &MftIndex, state: IndexState) {
+    index.set_state(state);
+    let _ = app.emit(
+        "index-state",
+        IndexStatePayload {
+            drive: index.drive_letter.clone(),
+            state,
+        },
+    );
 }
 
 #[derive(serde::Serialize)]
@@ -56,13 +120,349 @@ fn to_millis(time: std::io::Result<SystemTime>) -> Option<u64> {
         .map(|d| d.as_millis() as u64)
 }
 
+// SystemTime을 ZIP 항목 시간으로 변환 (ZIP은 로컬 시간 기준이며 1980~2107년만 표현 가능)
+fn to_zip_datetime(time: SystemTime) -> Option<zip::DateTime> {
+    use chrono::{Datelike, Timelike};
+    let local: chrono::DateTime<chrono::Local> = time.into();
+    zip::DateTime::from_date_and_time(
+        u16::try_from(local.year()).ok()?,
+        local.month() as u8,
+        local.day() as u8,
+        local.hour() as u8,
+        local.minute() as u8,
+        local.second() as u8,
+    )
+    .ok()
+}
+
+// deterministic 압축에 쓰는 고정 시간 (unix 초, 지정하지 않으면 ZIP이 표현할 수 있는 최소값 1980-01-01)
+// 어느 PC에서 만들어도 같은 결과가 나오도록 로컬 시간대가 아닌 UTC 기준으로 변환합니다.
+fn fixed_zip_datetime(epoch_secs: Option<i64>) -> Result<zip::DateTime, String> {
+    use chrono::{Datelike, Timelike};
+    let Some(secs) = epoch_secs else {
+        return Ok(zip::DateTime::default());
+    };
+    let utc = chrono::DateTime::from_timestamp(secs, 0)
+        .ok_or_else(|| format!("Invalid timestamp: {}", secs))?;
+    u16::try_from(utc.year())
+        .ok()
+        .and_then(|year| {
+            zip::DateTime::from_date_and_time(
+                year,
+                utc.month() as u8,
+                utc.day() as u8,
+                utc.hour() as u8,
+                utc.minute() as u8,
+                utc.second() as u8,
+            )
+            .ok()
+        })
+        .ok_or_else(|| format!("Timestamp {} is outside the ZIP date range (1980-2107)", secs))
+}
+
+// 원본 파일/폴더의 수정 시간을 가져오며, 유효하지 않으면 현재 시간을 사용
+fn source_zip_datetime(path: &Path) -> zip::DateTime {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(to_zip_datetime)
+        .or_else(|| to_zip_datetime(SystemTime::now()))
+        .unwrap_or_default()
+}
+
+// ZIP 항목 시간을 SystemTime으로 변환 (잘못된 날짜면 None)
+fn from_zip_datetime(time: zip::DateTime) -> Option<SystemTime> {
+    use chrono::TimeZone;
+    let naive = chrono::NaiveDate::from_ymd_opt(
+        time.year() as i32,
+        time.month() as u32,
+        time.day() as u32,
+    )?
+    .and_hms_opt(time.hour() as u32, time.minute() as u32, time.second() as u32)?;
+    chrono::Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+// 해제한 파일에 원본 수정 시간을 적용 (실패해도 해제는 계속 진행)
+fn restore_modified_time(file: &File, time: Option<zip::DateTime>) {
+    if let Some(t) = time.and_then(from_zip_datetime) {
+        let _ = file.set_modified(t);
+    }
+}
+
+// 압축 방식에 따라 허용되는 압축 레벨을 검증합니다.
+// stored는 레벨 개념이 없으므로 프론트엔드가 항상 값을 보내더라도 무시합니다.
+fn resolve_compression_level(
+    compression: zip::CompressionMethod,
+    level: Option<i64>,
+) -> Result<Option<i64>, String> {
+    let range = match compression {
+        zip::CompressionMethod::Stored => return Ok(None),
+        zip::CompressionMethod::Zstd => 1..=22,
+        zip::CompressionMethod::Bzip2 => 1..=9,
+        _ => 0..=9,
+    };
+    match level {
+        Some(l) if !range.contains(&l) => Err(format!(
+            "Invalid compression level {} for {:?} (expected {}-{})",
+            l,
+            compression,
+            range.start(),
+            range.end()
+        )),
+        _ => Ok(level),
+    }
+}
+
+// 압축/해제 I/O 버퍼 크기 (기본 64KB)
+// 4KB보다 작으면 시스템 호출이 너무 잦아지고, 16MB를 넘으면 병렬 압축 시 스레드마다
+// 버퍼를 잡으므로 메모리만 늘고 처리량은 거의 늘지 않습니다. 빠른 NVMe에서는 1MB 정도가 적당합니다.
+const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+const MIN_BUFFER_SIZE: usize = 4 * 1024;
+const MAX_BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+fn resolve_buffer_size(buffer_size: Option<usize>) -> Result<usize, String> {
+    match buffer_size {
+        None => Ok(DEFAULT_BUFFER_SIZE),
+        Some(size) if (MIN_BUFFER_SIZE..=MAX_BUFFER_SIZE).contains(&size) => Ok(size),
+        Some(size) => Err(format!(
+            "Invalid buffer size {} (expected {}-{} bytes)",
+            size, MIN_BUFFER_SIZE, MAX_BUFFER_SIZE
+        )),
+    }
+}
+
+// "C:"처럼 드라이브 문자로 시작하는 이름인지 (Path는 Windows에서만 드라이브 접두사로 인식하므로 직접 확인)
+fn has_drive_prefix(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+// ZIP 항목 이름을 대상 폴더 내부의 출력 경로로 변환합니다 (Zip Slip 방지).
+// 상위 경로(..), 절대 경로, 드라이브 접두사가 포함되거나 정규화 결과가 대상 폴더를 벗어나면
+// PATH_TRAVERSAL 오류를 반환합니다.
+fn safe_output_path(target_dir: &Path, entry_name: &str) -> Result<PathBuf, String> {
+    let traversal = || format!("PATH_TRAVERSAL: {}", entry_name);
+    if has_drive_prefix(entry_name) {
+        return Err(traversal());
+    }
+
+    let mut outpath = target_dir.to_path_buf();
+    for component in Path::new(&entry_name.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(part) => outpath.push(part),
+            Component::CurDir => {}
+            _ => return Err(traversal()),
+        }
+    }
+
+    // 이미 존재하는 가장 가까운 상위 폴더를 정규화하여 링크 등을 통해 벗어나는 경우도 차단
+    let root = target_dir.canonicalize().map_err(|e| e.to_string())?;
+    let mut existing = outpath.as_path();
+    while !existing.exists() {
+        match existing.parent() {
+            Some(parent) => existing = parent,
+            None => break,
+        }
+    }
+    let resolved = existing.canonicalize().map_err(|e| e.to_string())?;
+    if !resolved.starts_with(&root) {
+        return Err(traversal());
+    }
+    Ok(outpath)
+}
+
+// Windows 파일 이름에 쓸 수 없는 문자 (제어 문자 제외)
+const INVALID_NAME_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+// 해제할 때 쓸 수 없는 문자를 바꿀 문자 (기본값 '_')
+fn parse_name_substitute(substitute: Option<char>) -> Result<char, String> {
+    match substitute {
+        None => Ok('_'),
+        Some(c)
+            if INVALID_NAME_CHARS.contains(&c)
+                || c.is_control()
+                || matches!(c, '/' | '\\' | '.' | ' ') =>
+        {
+            Err(format!("Invalid name substitute: {:?}", c))
+        }
+        Some(c) => Ok(c),
+    }
+}
+
+// 항목 이름의 경로 요소마다 Windows에서 쓸 수 없는 문자를 substitute로 바꾸고 끝의 점/공백을 제거합니다.
+// 바뀐 것이 없으면 원래 이름을 그대로 반환합니다.
+// "."과 ".."은 safe_output_path에서 걸러지도록 그대로 두고, 점/공백만으로 된 요소는 substitute 하나로 바꿉니다.
+fn sanitize_entry_name(name: &str, substitute: char) -> std::borrow::Cow<'_, str> {
+    let needs_change = name.split(['/', '\\']).any(|part| {
+        part.contains(|c: char| INVALID_NAME_CHARS.contains(&c) || c.is_control())
+            || (part != "." && part != ".." && part.ends_with(['.', ' ']))
+    });
+    if !needs_change {
+        return name.into();
+    }
+    let parts: Vec<String> = name
+        .split(['/', '\\'])
+        .map(|part| {
+            if part == "." || part == ".." {
+                return part.to_string();
+            }
+            let replaced: String = part
+                .chars()
+                .map(|c| {
+                    if INVALID_NAME_CHARS.contains(&c) || c.is_control() {
+                        substitute
+                    } else {
+                        c
+                    }
+                })
+                .collect();
+            let trimmed = replaced.trim_end_matches(['.', ' ']);
+            if trimmed.is_empty() && !part.is_empty() {
+                substitute.to_string()
+            } else {
+                trimmed.to_string()
+            }
+        })
+        .collect();
+    parts.join("/").into()
+}
+
+// 선택한 이름(파일 또는 폴더 접두사)이 ZIP 항목과 일치하는지 확인합니다.
+fn entry_matches(selector: &str, name: &str) -> bool {
+    if selector == name {
+        return true;
+    }
+    if selector.ends_with('/') && name.starts_with(selector) {
+        return true;
+    }
+    name.starts_with(selector) && name[selector.len()..].starts_with('/')
+}
+
+// 해제할 항목 선택자
+// glob 문자(*, ?, [)가 있으면 패턴으로, 없으면 기존처럼 정확한 이름 또는 폴더 접두사로 비교합니다.
+// 패턴은 압축 필터와 같이 항목 전체 경로 또는 파일 이름과 비교합니다 (예: "*.jpg", "docs/**/*.pdf").
+enum EntrySelector {
+    Exact(String),
+    Pattern(glob::Pattern),
+}
+
+impl EntrySelector {
+    fn new(selector: &str) -> Result<Self, String> {
+        if selector.contains(['*', '?', '[']) {
+            glob::Pattern::new(selector)
+                .map(EntrySelector::Pattern)
+                .map_err(|e| format!("Invalid pattern '{}': {}", selector, e))
+        } else {
+            Ok(EntrySelector::Exact(selector.to_string()))
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            EntrySelector::Exact(selector) => entry_matches(selector, name),
+            EntrySelector::Pattern(pattern) => {
+                EntryFilter::matches_any(std::slice::from_ref(pattern), name)
+            }
+        }
+    }
+}
+
+// 레거시 코드 페이지 이름을 인코딩으로 변환합니다.
+// cp437은 zip 크레이트의 기본 해석과 같으므로 None으로 처리합니다.
+fn parse_name_encoding(
+    encoding: Option<&str>,
+) -> Result<Option<&'static encoding_rs::Encoding>, String> {
+    let Some(label) = encoding else {
+        return Ok(None);
+    };
+    match label.to_ascii_lowercase().as_str() {
+        "cp437" | "ibm437" => Ok(None),
+        "cp949" | "euc-kr" => Ok(Some(encoding_rs::EUC_KR)),
+        "cp932" | "shift_jis" | "shift-jis" => Ok(Some(encoding_rs::SHIFT_JIS)),
+        other => encoding_rs::Encoding::for_label(other.as_bytes())
+            .map(Some)
+            .ok_or_else(|| format!("Unknown filename encoding: {}", label)),
+    }
+}
+
+// 항목 이름에 UTF-8 플래그(범용 비트 11)가 있는지 확인합니다.
+// zip 크레이트는 플래그 값을 노출하지 않지만, 플래그(또는 Info-ZIP 유니코드 경로 필드)가 있는 이름만
+// 원래 바이트 그대로 UTF-8로 읽고 나머지는 CP437로 디코딩합니다. CP437은 0x80 이상 바이트를
+// 2-3바이트 문자로 바꾸므로, 디코딩된 이름이 원래 바이트와 같으면 플래그가 있는 것입니다 (ASCII 이름은 어느 쪽이든 같음).
+fn has_utf8_flag(file: &zip::read::ZipFile) -> bool {
+    file.name().as_bytes() == file.name_raw()
+}
+
+// UTF-8 플래그가 없는 항목 이름을 지정한 코드 페이지로 디코딩합니다.
+// (플래그 없이 저장된 CP949 이름이 우연히 올바른 UTF-8 바이트여도 코드 페이지로 디코딩)
+fn decode_entry_name(
+    file: &zip::read::ZipFile,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> String {
+    match encoding {
+        Some(enc) if !has_utf8_flag(file) => enc.decode(file.name_raw()).0.into_owned(),
+        _ => file.name().to_string(),
+    }
+}
+
+// 모든 항목 이름을 인덱스 순서대로 가져옵니다 (암호화된 항목도 복호화 없이 읽음).
+fn entry_names<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> Result<Vec<String>, String> {
+    (0..archive.len())
+        .map(|i| {
+            let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            Ok(decode_entry_name(&file, encoding))
+        })
+        .collect()
+}
+
 // 앱 상태 관리
 struct AppState {
-    mft: Arc<MftIndex>,
+    // 인덱싱 대상 드라이브("C:") -> 인덱스 (set_index_drives로 변경 가능)
+    mft: RwLock<BTreeMap<String, Arc<MftIndex>>>,
+    // 진행 중인 압축 작업들의 취소 요청 플래그
+    compress_cancel: CancelRegistry,
+    // 진행 중인 압축/해제 작업들의 일시 정지 상태
+    pause: PauseRegistry,
+    // 검색 결과에서 숨길 시스템 경로 (show_system이면 무시)
+    search_exclusions: RwLock<Vec<String>>,
+    // open_zip_entry로 풀어둔 임시 폴더 (앱 종료 시 삭제)
+    preview_dirs: Mutex<Vec<PathBuf>>,
+    // 경로 재구성 최대 깊이 (set_index_drives로 새로 추가되는 드라이브에도 적용)
+    max_path_depth: AtomicUsize,
+}
+
+impl AppState {
+    // 모든 드라이브의 인덱스 (드라이브 문자 순)
+    fn indexes(&self) -> Vec<Arc<MftIndex>> {
+        self.mft.read().unwrap().values().cloned().collect()
+    }
+
+    fn index_for(&self, drive: &str) -> Result<Arc<MftIndex>, String> {
+        let drive = normalize_drive_letter(drive)?;
+        self.mft
+            .read()
+            .unwrap()
+            .get(&drive)
+            .cloned()
+            .ok_or_else(|| format!("Drive is not indexed: {}", drive))
+    }
+
+    // 임시 폴더 삭제 (연결 프로그램이 아직 파일을 열고 있으면 실패할 수 있으므로 오류는 무시)
+    fn cleanup_preview_dirs(&self) {
+        for dir in self.preview_dirs.lock().unwrap().drain(..) {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
 }
 
-/// 앱 데이터 디렉터리에 인덱스 파일 경로를 가져옵니다.
-fn get_index_path(app: &AppHandle) -> Result<PathBuf, String> {
+/// 앱 설정 디렉터리를 가져오며, 없으면 생성합니다.
+fn get_app_config_dir(app: &AppHandle) -> Result<PathBuf, String> {
     let dir = app
         .path()
         .app_config_dir()
@@ -72,252 +472,3309 @@ fn get_index_path(app: &AppHandle) -> Result<PathBuf, String> {
         std::fs::create_dir_all(&dir)
             .map_err(|e| format!("Failed to create app config directory: {}", e))?;
     }
-    Ok(dir.join("mft_index.bin"))
+    Ok(dir)
+}
+
+/// 앱 데이터 디렉터리에 드라이브별 인덱스 파일 경로를 가져옵니다 (예: "mft_index_C.bin").
+fn get_index_path(app: &AppHandle, drive: &str) -> Result<PathBuf, String> {
+    let letter = drive.trim_end_matches(':');
+    Ok(get_app_config_dir(app)?.join(format!("mft_index_{}.bin", letter)))
+}
+
+/// 드라이브 하나만 인덱싱하던 버전의 인덱스 파일 경로 (첫 번째 드라이브의 인덱스로 옮겨 사용)
+fn get_legacy_index_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_config_dir(app)?.join("mft_index.bin"))
+}
+
+/// 인덱싱 대상 드라이브 설정 파일 경로를 가져옵니다 (한 줄에 드라이브 하나).
+fn get_index_drive_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_config_dir(app)?.join("index_drive.txt"))
+}
+
+fn get_search_exclusions_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_app_config_dir(app)?.join("search_exclusions.txt"))
+}
+
+/// 저장된 검색 제외 경로 목록(한 줄에 하나)을 읽어옵니다. 설정이 없으면 기본 목록을 사용합니다.
+fn load_search_exclusions(app: &AppHandle) -> Vec<String> {
+    get_search_exclusions_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|s| {
+            s.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_else(|| DEFAULT_SEARCH_EXCLUSIONS.iter().map(|s| s.to_string()).collect())
+}
+
+/// 저장된 인덱싱 대상 드라이브 목록을 읽어옵니다. 설정이 없으면 C: 를 사용합니다.
+fn load_index_drives(app: &AppHandle) -> Vec<String> {
+    let mut drives: Vec<String> = get_index_drive_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|s| s.lines().filter_map(|line| normalize_drive_letter(line).ok()).collect())
+        .unwrap_or_default();
+    drives.sort();
+    drives.dedup();
+    if drives.is_empty() {
+        drives.push("C:".to_string());
+    }
+    drives
+}
+
+/// "d", "D:", "D:\" 등의 입력을 "D:" 형식으로 정규화합니다.
+fn normalize_drive_letter(drive: &str) -> Result<String, String> {
+    let trimmed = drive.trim().trim_end_matches(['\\', '/']).trim_end_matches(':');
+    let mut chars = trimmed.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => Ok(format!("{}:", c.to_ascii_uppercase())),
+        _ => Err(format!("Invalid drive letter: {}", drive)),
+    }
+}
+
+/// 인덱싱이 가능한 고정 볼륨인지 확인합니다.
+/// NTFS가 아닌 볼륨(FAT32, exFAT 등)은 폴더 탐색 방식으로 인덱싱됩니다.
+fn validate_index_drive(drive: &str) -> Result<(), String> {
+    let disks = Disks::new_with_refreshed_list();
+    let disk = disks
+        .iter()
+        .find(|d| {
+            normalize_drive_letter(&d.mount_point().to_string_lossy()).as_deref() == Ok(drive)
+        })
+        .ok_or_else(|| format!("Drive not found: {}", drive))?;
+
+    if disk.is_removable() {
+        return Err(format!("Drive {} is removable and cannot be indexed", drive));
+    }
+    Ok(())
+}
+
+/// 인덱싱 대상 드라이브 목록을 반환합니다.
+#[tauri::command]
+fn get_index_drives(state: tauri::State<'_, AppState>) -> Vec<String> {
+    state.mft.read().unwrap().keys().cloned().collect()
+}
+
+/// 드라이브별 인덱스 상태를 반환합니다 (drive를 생략하면 모든 드라이브).
+/// "index-ready" 이벤트는 한 번만 전송되므로, 화면이 늦게 열려도 시작할 때 이 명령으로 현재 상태를 확인할 수 있습니다.
+/// 상태가 바뀔 때마다 같은 형태의 "index-state" 이벤트도 전송됩니다.
+#[tauri::command]
+fn get_index_state(
+    state: tauri::State<'_, AppState>,
+    drive: Option<String>,
+) -> Result<Vec<IndexStatePayload>, CommandError> {
+    let indexes = match drive {
+        Some(drive) => vec![state.index_for(&drive)?],
+        None => state.indexes(),
+    };
+    Ok(indexes
+        .iter()
+        .map(|index| IndexStatePayload {
+            drive: index.drive_letter.clone(),
+            state: index.state(),
+        })
+        .collect())
+}
+
+/// 드라이브의 인덱스 방식을 반환합니다 (drive를 생략하면 첫 번째 드라이브).
+/// "mft"는 USN 저널로 실시간 갱신되고, "walk"는 폴더 탐색으로 만든 인덱스라 실시간 갱신이 없습니다.
+#[tauri::command]
+fn get_index_mode(
+    state: tauri::State<'_, AppState>,
+    drive: Option<String>,
+) -> Result<String, CommandError> {
+    let index = match drive {
+        Some(drive) => state.index_for(&drive)?,
+        None => state.indexes().into_iter().next().ok_or("No drive is indexed")?,
+    };
+    Ok(if index.is_walk_based() { "walk" } else { "mft" }.to_string())
+}
+
+/// 인덱스만으로 폴더의 바로 아래 항목을 반환합니다 (검색 결과의 "폴더에서 보기"용).
+/// 디스크를 읽지 않으므로 느린 네트워크 경로에서도 바로 응답하지만, 크기와 수정 시각은 인덱스에 캐시된 값만 채워집니다.
+/// 인덱싱되지 않은 드라이브나 인덱스에 없는 폴더면 오류를 반환합니다.
+#[tauri::command(async)]
+fn list_directory_from_index(
+    state: tauri::State<'_, AppState>,
+    dir_path: String,
+) -> Result<Vec<DirChild>, CommandError> {
+    let drive = dir_path
+        .get(..2)
+        .ok_or_else(|| format!("Invalid path: {}", dir_path))?;
+    let index = state.index_for(drive)?;
+    Ok(index
+        .list_dir(&dir_path)
+        .ok_or_else(|| format!("Directory is not indexed: {}", dir_path))?)
+}
+
+#[derive(serde::Serialize)]
+struct IndexStatsResult {
+    drive: String,
+    #[serde(flatten)]
+    index: IndexStats,
+    // 디스크의 인덱스 파일 크기와 수정 시각 (unix millis), 아직 저장되지 않았으면 None
+    #[serde(rename = "fileSize")]
+    file_size: Option<u64>,
+    #[serde(rename = "fileModified")]
+    file_modified: Option<u64>,
+}
+
+/// 드라이브별 인덱스 통계를 반환합니다 (항목 수, 메모리 사용량, 저장된 USN 정보, 인덱스 파일 정보).
+/// "검색 결과가 비어 있음" 같은 상황을 진단할 때 사용합니다.
+/// 메모리 사용량 계산에 전체 항목을 순회하므로 메인 스레드가 아닌 별도 스레드에서 실행합니다.
+#[tauri::command(async)]
+fn get_index_stats(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<IndexStatsResult>, CommandError> {
+    let mut stats = Vec::new();
+    for index in state.indexes() {
+        let metadata = fs::metadata(get_index_path(&app, &index.drive_letter)?).ok();
+        stats.push(IndexStatsResult {
+            drive: index.drive_letter.clone(),
+            index: index.stats(),
+            file_size: metadata.as_ref().map(|m| m.len()),
+            file_modified: metadata.and_then(|m| to_millis(m.modified())),
+        });
+    }
+    Ok(stats)
+}
+
+#[derive(serde::Serialize)]
+struct ValidateIndexResult {
+    drive: String,
+    #[serde(flatten)]
+    report: ValidationReport,
+}
+
+/// 인덱스를 디스크와 비교해 없는 파일이 남아 있거나 새 파일이 빠진 상태를 찾습니다 (drive를 생략하면 모든 드라이브).
+/// sample을 지정하면 그 개수만큼만 골라 확인하므로 어긋남이 있는지 빠르게 점검할 수 있습니다.
+/// repair면 없는 항목을 지우고, rescan이면 확인한 폴더를 다시 읽어 빠진 항목을 추가한 뒤 인덱스 파일을 저장합니다.
+/// 어긋난 항목이 적을 때 전체 재인덱싱 없이 복구하는 용도입니다.
+#[tauri::command(async)]
+fn validate_index(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    drive: Option<String>,
+    sample: Option<usize>,
+    repair: Option<bool>,
+    rescan: Option<bool>,
+) -> Result<Vec<ValidateIndexResult>, CommandError> {
+    let indexes = match drive {
+        Some(drive) => vec![state.index_for(&drive)?],
+        None => state.indexes(),
+    };
+    let mut results = Vec::new();
+    for index in indexes {
+        let report = index.validate(sample, repair.unwrap_or(false), rescan.unwrap_or(false))?;
+        if report.removed > 0 || report.added > 0 {
+            let index_path = get_index_path(&app, &index.drive_letter)?;
+            index.save_to_disk(&index_path, index.current_usn(), index.journal_id())?;
+        }
+        results.push(ValidateIndexResult {
+            drive: index.drive_letter.clone(),
+            report,
+        });
+    }
+    Ok(results)
+}
+
+/// 경로 재구성 최대 깊이를 변경합니다 (기본값 50, 모든 드라이브에 적용).
+/// 폴더가 아주 깊게 중첩되어 get_index_stats의 truncatedPaths가 0이 아니면 늘려서 그 아래 항목도 검색되게 합니다.
+/// 설정은 저장되지 않으므로 앱을 다시 시작하면 기본값으로 돌아갑니다.
+#[tauri::command]
+fn set_max_path_depth(state: tauri::State<'_, AppState>, depth: usize) -> Result<(), CommandError> {
+    if depth == 0 {
+        return Err("Depth must be at least 1".into());
+    }
+    state.max_path_depth.store(depth, Ordering::SeqCst);
+    for index in state.indexes() {
+        index.set_max_path_depth(depth);
+    }
+    Ok(())
+}
+
+/// 검색 결과에서 숨기는 경로 목록을 반환합니다.
+#[tauri::command]
+fn get_search_exclusions(state: tauri::State<'_, AppState>) -> Vec<String> {
+    state.search_exclusions.read().unwrap().clone()
+}
+
+/// 검색 결과에서 숨기는 경로 목록을 변경하고 저장합니다 (드라이브 루트 기준, 예: "Users/*/AppData").
+#[tauri::command]
+fn set_search_exclusions(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    exclusions: Vec<String>,
+) -> Result<(), CommandError> {
+    let exclusions: Vec<String> = exclusions
+        .iter()
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .collect();
+    fs::write(get_search_exclusions_path(&app)?, exclusions.join("\n"))
+        .map_err(|e| e.to_string())?;
+    *state.search_exclusions.write().unwrap() = exclusions;
+    Ok(())
+}
+
+/// 인덱싱 대상 드라이브 목록을 변경합니다. 새로 추가된 드라이브는 build_mft_index로 인덱싱해야 합니다.
+/// 목록에서 빠진 드라이브는 모니터링을 멈추고 인덱스 파일을 삭제하며, 그대로 남은 드라이브의 인덱스는 유지합니다.
+#[tauri::command]
+fn set_index_drives(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    drives: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    let mut drives = drives
+        .iter()
+        .map(|d| normalize_drive_letter(d))
+        .collect::<Result<Vec<_>, _>>()?;
+    drives.sort();
+    drives.dedup();
+    if drives.is_empty() {
+        return Err("At least one drive must be indexed".into());
+    }
+    for drive in &drives {
+        validate_index_drive(drive)?;
+    }
+
+    let mut indexes = state.mft.write().unwrap();
+    let removed: Vec<String> = indexes
+        .keys()
+        .filter(|d| !drives.contains(d))
+        .cloned()
+        .collect();
+    for drive in removed {
+        if let Some(old) = indexes.remove(&drive) {
+            old.stop_monitoring();
+        }
+        let index_path = get_index_path(&app, &drive)?;
+        if index_path.exists() {
+            fs::remove_file(&index_path).map_err(|e| e.to_string())?;
+        }
+    }
+    for drive in &drives {
+        indexes.entry(drive.clone()).or_insert_with(|| {
+            let index = MftIndex::new(drive.clone());
+            index.set_max_path_depth(state.max_path_depth.load(Ordering::SeqCst));
+            Arc::new(index)
+        });
+    }
+    fs::write(get_index_drive_path(&app)?, drives.join("\n")).map_err(|e| e.to_string())?;
+
+    Ok(drives)
 }
 
+// drive를 지정하면 그 드라이브만, 생략하면 인덱싱 대상 드라이브를 모두 차례로 인덱싱합니다.
+// 반환값은 인덱싱된 전체 항목 수입니다.
 #[tauri::command]
 async fn build_mft_index(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
-) -> Result<usize, String> {
-    let index_for_build = state.mft.clone();
+    drive: Option<String>,
+) -> Result<usize, CommandError> {
+    let indexes = match drive {
+        Some(drive) => vec![state.index_for(&drive)?],
+        None => state.indexes(),
+    };
+    let mut total = 0;
+    for index in indexes {
+        total += build_drive_index(&app, index).await?;
+    }
+    Ok(total)
+}
+
+// 드라이브 하나를 인덱싱하고 저장한 뒤 모니터링을 시작합니다.
+// 인덱싱하는 동안 상태는 Building이며, 끝나면 Ready(실패하면 NotBuilt)가 됩니다.
+async fn build_drive_index(app: &AppHandle, index: Arc<MftIndex>) -> Result<usize, String> {
+    set_index_state(app, &index, IndexState::Building);
+    let result = build_and_save_index(app, index.clone()).await;
+    match result {
+        Ok((count, next_usn, journal_id)) => {
+            start_monitor(app.clone(), index.clone(), next_usn, journal_id);
+            set_index_state(app, &index, IndexState::Ready);
+            Ok(count)
+        }
+        Err(e) => {
+            set_index_state(app, &index, IndexState::NotBuilt);
+            Err(e)
+        }
+    }
+}
+
+// 인덱싱 후 인덱스 파일로 저장하고 (항목 수, next_usn, journal_id)를 반환합니다.
+async fn build_and_save_index(
+    app: &AppHandle,
+    index: Arc<MftIndex>,
+) -> Result<(usize, i64, u64), String> {
+    let index_for_build = index.clone();
+    let app_for_progress = app.clone();
 
     // build_index는 CPU 집약적이고 동기적인 함수이므로, 비동기 런타임이 차단되지 않도록 별도 스레드에서 실행합니다.
-    let (count, next_usn, journal_id) =
-        tauri::async_runtime::spawn_blocking(move || index_for_build.build_index())
-            .await
-            .map_err(|e| e.to_string())??; // JoinError 처리 후 build_index의 Result 처리
+    // 전체 개수를 미리 알 수 없으므로, 인덱싱 중 지금까지 처리한 개수를 "index-progress"로 전송합니다.
+    let build_result = tauri::async_runtime::spawn_blocking(move || {
+        let drive = index_for_build.drive_letter.clone();
+        let mut emit_progress = |processed| {
+            let _ = app_for_progress.emit(
+                "index-progress",
+                IndexProgressPayload {
+                    drive: drive.clone(),
+                    processed,
+                },
+            );
+        };
+        index_for_build.build_index(Some(&mut emit_progress))
+    })
+    .await
+    .map_err(|e| e.to_string());
+    let (count, next_usn, journal_id) = build_result??; // JoinError 처리 후 build_index의 Result 처리
 
     // 인덱스 파일 저장 (이것도 I/O 작업이므로 spawn_blocking 사용)
-    let index_for_save = state.mft.clone();
-    let index_path = get_index_path(&app)?;
+    let index_for_save = index.clone();
+    let index_path = get_index_path(app, &index.drive_letter)?;
     tauri::async_runtime::spawn_blocking(move || {
         index_for_save.save_to_disk(&index_path, next_usn, journal_id)
     })
     .await
     .map_err(|e| e.to_string())??;
 
-    // 모니터링은 무한 루프이므로 별도의 OS 스레드에서 실행합니다.
-    let index_for_monitor = state.mft.clone();
-    let app_for_monitor = app.clone();
+    Ok((count, next_usn, journal_id))
+}
+
+// 실시간 모니터링 종료 명령어
+#[tauri::command]
+fn stop_monitoring(state: tauri::State<'_, AppState>) {
+    for index in state.indexes() {
+        index.stop_monitoring();
+    }
+}
+
+// USN 저널 모니터링 시작
+// 모니터링은 무한 루프이므로 드라이브마다 별도의 OS 스레드에서 실행합니다.
+// 저널을 더 이상 이어서 읽을 수 없으면 "index-stale" 이벤트로 재인덱싱이 필요함을 알립니다.
+// 폴더 탐색 기반 인덱스는 USN 저널이 없으므로 모니터링하지 않습니다.
+// 비정상 종료 시 실시간 변경 내역을 잃지 않도록 일정 개수 또는 일정 시간마다 인덱스를 저장합니다.
+// (현재는 전체를 다시 저장하며, 저장 간격이 I/O 부담을 제한합니다)
+fn start_monitor(app: AppHandle, index: Arc<MftIndex>, next_usn: i64, journal_id: u64) {
+    const SAVE_EVERY_CHANGES: usize = 10_000;
+    const SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+    if index.is_walk_based() {
+        return;
+    }
+    let index_path = get_index_path(&app, &index.drive_letter).ok();
     std::thread::spawn(move || {
-        index_for_monitor.monitor(next_usn, journal_id, move |changes| {
-            let _ = app_for_monitor.emit("file-changes", changes);
-        });
+        let app_for_changes = app.clone();
+        let index_for_save = index.clone();
+        // (마지막 저장 이후 변경 개수, 마지막 저장 시각)
+        let unsaved = Mutex::new((0usize, Instant::now()));
+        index.monitor(
+            next_usn,
+            journal_id,
+            DEFAULT_REASON_MASK,
+            move |changes| {
+                let count = changes.len();
+                let _ = app_for_changes.emit("file-changes", changes);
+
+                let Some(path) = &index_path else { return };
+                let mut unsaved = unsaved.lock().unwrap();
+                unsaved.0 += count;
+                if unsaved.0 >= SAVE_EVERY_CHANGES || unsaved.1.elapsed() >= SAVE_INTERVAL {
+                    let usn = index_for_save.current_usn();
+                    if let Err(e) = index_for_save.save_to_disk(path, usn, journal_id) {
+                        println!("Failed to save index: {}", e);
+                    }
+                    *unsaved = (0, Instant::now());
+                }
+            },
+            |reason| {
+                // 어느 드라이브를 다시 인덱싱해야 하는지 알 수 있도록 드라이브를 앞에 붙임
+                let reason = format!("{}: {}", index.drive_letter, reason);
+                println!("Index is stale: {}", reason);
+                set_index_state(&app, &index, IndexState::Stale);
+                let _ = app.emit("index-stale", reason);
+            },
+        );
     });
+}
 
-    Ok(count)
+#[derive(serde::Serialize)]
+struct SearchResult {
+    paths: Vec<String>,
+    // paths와 같은 순서의 일치 점수 (0-100, 퍼지 검색 외에는 모두 100)
+    scores: Vec<u32>,
+    // paths와 같은 순서의 결과 드라이브 (예: "D:")
+    drives: Vec<String>,
+    // offset/limit으로 자르기 전의 전체 일치 개수
+    total: usize,
 }
 
+// mode: "substring" | "literal" | "glob" | "regex" | "fuzzy"
+// substring은 공백으로 나눈 단어가 모두 이름에 있어야 일치하며 ("..."로 묶으면 구절 그대로 검색),
+// literal은 검색어 전체를 나누지 않고 하나의 부분 문자열로 검색합니다.
+// fuzzy는 짧은 오타(편집 거리 1-2)를 허용합니다.
+// 정렬 기준이 없으면 관련성 순(이름이 같음 > 앞부분 일치 > 부분 일치 > 퍼지 일치)으로 정렬해 반환합니다.
+// offset/limit으로 정렬된 결과의 일부만 받을 수 있으며, 정렬 순서는 요청마다 같습니다 (페이지 이동용).
+// 인덱싱된 모든 드라이브를 함께 검색하며, 드라이브가 달라도 같은 기준으로 정렬됩니다.
+// case_sensitive면 이름의 대소문자를 구분합니다 (기본값은 구분하지 않음).
 #[tauri::command]
 async fn search_mft(
     state: tauri::State<'_, AppState>,
     query: String,
     use_regex: bool,
-) -> Result<Vec<String>, String> {
-    let paths = state.mft.search(&query, use_regex);
+    mode: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    ext: Option<String>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<i64>,
+    modified_before: Option<i64>,
+    sort: Option<String>,
+    show_system: Option<bool>,
+    case_sensitive: Option<bool>,
+) -> Result<SearchResult, CommandError> {
+    // mode가 없으면 기존 use_regex 플래그를 따름
+    let mode = match mode.as_deref() {
+        Some(m) => SearchMode::parse(m)?,
+        None if use_regex => SearchMode::Regex,
+        None => SearchMode::Substring,
+    };
+    let filter = SearchFilter {
+        ext: ext.filter(|e| !e.trim().is_empty()),
+        min_size,
+        max_size,
+        modified_after,
+        modified_before,
+        sort: sort.as_deref().map(SearchSort::parse).transpose()?,
+        exclude_paths: if show_system.unwrap_or(false) {
+            Vec::new()
+        } else {
+            state.search_exclusions.read().unwrap().clone()
+        },
+        case_sensitive: case_sensitive.unwrap_or(false),
+    };
+    let (hits, total) = search_all(
+        &state.indexes(),
+        &query,
+        mode,
+        &filter,
+        offset.unwrap_or(0),
+        limit,
+    )?;
     // PathBuf를 String으로 변환하여 반환
-    Ok(paths
-        .into_iter()
-        .map(|p| p.to_string_lossy().into_owned())
-        .collect())
+    Ok(SearchResult {
+        scores: hits.iter().map(|hit| hit.score).collect(),
+        drives: hits.iter().map(|hit| hit.drive.clone()).collect(),
+        paths: hits
+            .into_iter()
+            .map(|hit| hit.path.to_string_lossy().into_owned())
+            .collect(),
+        total,
+    })
 }
 
-// 압축 명령어
-#[tauri::command]
-fn compress_files(
-    window: Window,
-    paths: Vec<String>,
-    target_zip_path: String,
-    method: Option<String>,
-    password: Option<String>,
-    encryption_mode: Option<String>,
-) -> Result<(), String> {
-    let path = Path::new(&target_zip_path);
-    let file = File::create(&path).map_err(|e| e.to_string())?;
-    let mut zip = zip::ZipWriter::new(file);
-
-    let compression = match method.as_deref().unwrap_or("deflated") {
-        "stored" => zip::CompressionMethod::Stored,
-        _ => zip::CompressionMethod::Deflated,
-    };
+// 압축할 항목 (원본 경로 -> ZIP 내부 경로)
+struct PlannedEntry {
+    source: PathBuf,
+    name: String,
+    is_dir: bool,
+    size: u64,
+    // true면 압축 방식과 관계없이 무압축(stored)으로 저장 ("smart" 방식 참고)
+    store: bool,
+    // 지정하면 원본 수정 시간 대신 이 시간을 기록 (deterministic 압축 참고)
+    fixed_time: Option<zip::DateTime>,
+}
 
-    let mut options = FileOptions::<()>::default()
-        .compression_method(compression)
-        .unix_permissions(0o755);
+// "smart" 방식에서 기본으로 무압축 저장하는 확장자 (이미 압축된 형식이라 deflate 이득이 거의 없음)
+const DEFAULT_STORE_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "webp", "heic", "mp3", "aac", "ogg", "flac", "m4a", "mp4",
+    "mkv", "avi", "mov", "webm", "zip", "7z", "rar", "gz", "bz2", "xz", "zst", "docx", "xlsx",
+    "pptx",
+];
 
-    if let Some(ref pass) = password {
-        if encryption_mode.as_deref() == Some("aes256") {
-            options = options.with_aes_encryption(zip::AesMode::Aes256, pass);
-        } else {
-            options = options.with_deprecated_encryption(pass.as_bytes());
-        }
+// 확장자 목록(대소문자 무시, 앞의 '.'은 생략 가능)에 해당하는 파일을 무압축 저장하도록 표시
+fn mark_stored_entries(entries: &mut [PlannedEntry], extensions: &[String]) {
+    let extensions: Vec<String> = extensions
+        .iter()
+        .map(|e| e.trim_start_matches('.').to_lowercase())
+        .collect();
+    for entry in entries.iter_mut().filter(|e| !e.is_dir) {
+        let ext = Path::new(&entry.name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        entry.store = ext.is_some_and(|ext| extensions.contains(&ext));
     }
+}
 
-    // 1. 전체 크기 계산 (진행률 표시용)
-    let mut total_size = 0u64;
-    for src_path_str in &paths {
-        let src_path = Path::new(src_path_str);
-        if src_path.is_dir() {
-            for entry in WalkDir::new(src_path) {
-                let entry = entry.map_err(|e| e.to_string())?;
-                if entry.file_type().is_file() {
-                    total_size += entry.metadata().map_err(|e| e.to_string())?.len();
-                }
-            }
-        } else {
-            total_size += fs::metadata(src_path).map_err(|e| e.to_string())?.len();
+// "adaptive" 방식에서 압축해 볼 파일 앞부분 크기
+const ADAPTIVE_SAMPLE_SIZE: u64 = 64 * 1024;
+// 앞부분을 압축한 크기가 원래 크기의 이 비율보다 크면 이득이 거의 없다고 보고 무압축 저장
+const ADAPTIVE_STORE_RATIO: f64 = 0.95;
+
+// 파일 앞부분을 deflate(빠른 레벨)로 압축해 보고 이득이 거의 없는 파일을 무압축 저장하도록 표시
+// 확장자로 알 수 없는 이미 압축된 데이터(이름이 바뀐 압축 파일, 암호화된 파일 등)도 걸러내며 파일들을 병렬로 확인합니다.
+// 읽을 수 없는 파일은 그대로 두며, 오류는 실제로 압축할 때 보고됩니다.
+fn mark_incompressible_entries(entries: &mut [PlannedEntry]) {
+    entries
+        .par_iter_mut()
+        .filter(|e| !e.is_dir && e.size > 0)
+        .for_each(|entry| entry.store = sample_is_incompressible(&entry.source));
+}
+
+fn sample_is_incompressible(path: &Path) -> bool {
+    let mut sample = Vec::new();
+    let read =
+        File::open(path).and_then(|file| file.take(ADAPTIVE_SAMPLE_SIZE).read_to_end(&mut sample));
+    if read.is_err() || sample.is_empty() {
+        return false;
+    }
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::fast());
+    let compressed = encoder
+        .write_all(&sample)
+        .and_then(|_| encoder.finish())
+        .map_or(0, |out| out.len());
+    compressed as f64 > sample.len() as f64 * ADAPTIVE_STORE_RATIO
+}
+
+// 항목별 옵션 (수정 시간, store로 표시된 항목은 무압축, 암호화 설정은 유지)
+fn entry_file_options<'k>(entry: &PlannedEntry, options: FileOptions<'k, ()>) -> FileOptions<'k, ()> {
+    let modified = entry
+        .fixed_time
+        .unwrap_or_else(|| source_zip_datetime(&entry.source));
+    let options = options.last_modified_time(modified);
+    if entry.store {
+        options
+            .compression_method(zip::CompressionMethod::Stored)
+            .compression_level(None)
+    } else {
+        options
+    }
+}
+
+// 압축 대상 필터 (include/exclude glob 패턴)
+// 패턴은 ZIP 내부 상대 경로 전체 또는 파일/폴더 이름과 비교합니다 (예: "node_modules", "*.tmp", "src/**/*.rs").
+// 두 목록에 모두 해당하면 exclude가 우선하며, 제외된 폴더는 하위로 내려가지 않습니다.
+// include는 파일에만 적용되고, include가 비어 있으면 제외되지 않은 모든 항목을 포함합니다.
+#[derive(Default)]
+struct EntryFilter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl EntryFilter {
+    fn new(include: &[String], exclude: &[String]) -> Result<Self, String> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| {
+                    glob::Pattern::new(p).map_err(|e| format!("Invalid pattern '{}': {}", p, e))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    fn matches_any(patterns: &[glob::Pattern], name: &str) -> bool {
+        let file_name = name.trim_end_matches('/').rsplit('/').next().unwrap_or(name);
+        patterns
+            .iter()
+            .any(|p| p.matches(name) || p.matches(file_name))
+    }
+
+    fn is_excluded(&self, name: &str) -> bool {
+        Self::matches_any(&self.exclude, name)
+    }
+
+    fn is_included(&self, name: &str, is_dir: bool) -> bool {
+        if self.is_excluded(name) {
+            return false;
+        }
+        if self.include.is_empty() {
+            return true;
         }
+        !is_dir && Self::matches_any(&self.include, name)
     }
+}
 
-    let mut processed_size = 0u64;
-    let mut last_emit = Instant::now();
-    let mut buffer = [0u8; 65536]; // 64KB 버퍼
+// ZIP 내부 경로 계산 (기준 폴더에 대한 상대 경로, 구분자는 '/')
+fn zip_entry_name(path: &Path, base: &Path) -> Result<String, String> {
+    let name = path.strip_prefix(base).map_err(|e| e.to_string())?;
+    Ok(name.to_str().ok_or("Invalid path")?.replace("\\", "/"))
+}
+
+// 모든 항목 앞에 붙일 ZIP 내부 경로 (구분자는 '/'로 통일하고 끝에 '/'를 붙임, 비어 있으면 "")
+// "."이나 ".." 요소, 드라이브 문자(':')처럼 파일 이름에 쓸 수 없는 문자, 제어 문자가 있으면 오류
+fn normalize_base_prefix(prefix: &str) -> Result<String, String> {
+    let parts: Vec<&str> = prefix
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty())
+        .collect();
+    let invalid = parts.iter().any(|part| {
+        *part == "."
+            || *part == ".."
+            || part.contains(|c: char| INVALID_NAME_CHARS.contains(&c) || c.is_control())
+    });
+    if invalid {
+        return Err(format!("Invalid base prefix: {}", prefix));
+    }
+    if parts.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(format!("{}/", parts.join("/")))
+}
+
+// 압축할 폴더 안의 심볼릭 링크/정션(reparse point)의 처리 방식
+// follow는 링크 대상을 그대로 순회하며, 상위 폴더로 되돌아가는 링크(순환)는 건너뜁니다.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LinkPolicy {
+    Skip,
+    Follow,
+}
+
+impl LinkPolicy {
+    fn parse(policy: &str) -> Result<Self, String> {
+        match policy {
+            "skip" => Ok(LinkPolicy::Skip),
+            "follow" => Ok(LinkPolicy::Follow),
+            other => Err(format!("Unknown link policy: {}", other)),
+        }
+    }
+}
+
+// 링크로 취급할 항목인지 (링크 자체의 메타데이터 기준)
+// Windows에서는 reparse point 속성이 있는 폴더(정션, 마운트 지점)도 링크로 봅니다.
+// OneDrive 클라우드 파일처럼 reparse point인 일반 파일은 내용이 있는 파일이므로 포함합니다.
+#[cfg(windows)]
+fn is_link(meta: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+    meta.file_type().is_symlink()
+        || (meta.is_dir() && meta.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0)
+}
+
+#[cfg(not(windows))]
+fn is_link(meta: &fs::Metadata) -> bool {
+    meta.file_type().is_symlink()
+}
 
+// 선택된 경로들을 순회하여 ZIP에 추가할 항목 목록을 만듭니다.
+// 폴더 안의 링크는 links에 따라 건너뛰거나 따라가며, 건너뛴 링크(따라갈 때는 순환 링크)의
+// ZIP 내부 경로를 skipped_links에 담습니다. 직접 선택한 경로는 링크여도 그대로 압축합니다.
+fn plan_entries(
+    paths: &[String],
+    filter: &EntryFilter,
+    links: LinkPolicy,
+    skipped_links: &mut Vec<String>,
+) -> Result<Vec<PlannedEntry>, String> {
+    let mut entries = Vec::new();
     for src_path_str in paths {
-        let src_path = Path::new(&src_path_str);
+        let src_path = Path::new(src_path_str);
 
         // 폴더인 경우 재귀적으로 추가
         if src_path.is_dir() {
-            let walk = WalkDir::new(src_path);
+            let base = src_path.parent().unwrap_or(Path::new("/"));
+            let mut cycles = Vec::new();
+            // 제외된 폴더와 건너뛸 링크는 하위 항목을 순회하지 않음
+            let walk = WalkDir::new(src_path)
+                .follow_links(links == LinkPolicy::Follow)
+                .into_iter()
+                .filter_entry(|e| {
+                    let Ok(name) = zip_entry_name(e.path(), base) else {
+                        return true;
+                    };
+                    if filter.is_excluded(&name) {
+                        return false;
+                    }
+                    let link = links == LinkPolicy::Skip
+                        && e.depth() > 0
+                        && e.metadata().is_ok_and(|m| is_link(&m));
+                    if link {
+                        skipped_links.push(name);
+                    }
+                    !link
+                });
             for entry in walk {
-                let entry = entry.map_err(|e| e.to_string())?;
-                let path = entry.path();
-
-                // ZIP 내부 경로 계산 (상대 경로)
-                let name = path
-                    .strip_prefix(src_path.parent().unwrap_or(Path::new("/")))
-                    .map_err(|e| e.to_string())?;
-                let path_as_string = name.to_str().ok_or("Invalid path")?.replace("\\", "/");
-
-                if path.is_dir() {
-                    zip.add_directory(path_as_string, options)
-                        .map_err(|e| e.to_string())?;
-                } else {
-                    zip.start_file(path_as_string.clone(), options)
-                        .map_err(|e| e.to_string())?;
-                    let f = File::open(path).map_err(|e| e.to_string())?;
-                    let mut reader = BufReader::new(f);
-
-                    loop {
-                        let n = reader.read(&mut buffer).map_err(|e| e.to_string())?;
-                        if n == 0 {
-                            break;
-                        }
-                        zip.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
-
-                        processed_size += n as u64;
-                        if last_emit.elapsed().as_millis() > 100 {
-                            // 0.1초마다 이벤트 전송
-                            window
-                                .emit(
-                                    "compress-progress",
-                                    ProgressPayload {
-                                        total: total_size,
-                                        processed: processed_size,
-                                        filename: path_as_string.to_string(),
-                                    },
-                                )
-                                .map_err(|e| e.to_string())?;
-                            last_emit = Instant::now();
+                let entry = match entry {
+                    // 따라간 링크가 상위 폴더를 가리키면 순환이므로 건너뜀
+                    Err(e) if e.loop_ancestor().is_some() => {
+                        if let Some(path) = e.path() {
+                            cycles.push(zip_entry_name(path, base)?);
                         }
+                        continue;
                     }
+                    entry => entry.map_err(|e| e.to_string())?,
+                };
+                let path = entry.path();
+                let name = zip_entry_name(path, base)?;
+
+                let is_dir = path.is_dir();
+                if !filter.is_included(&name, is_dir) {
+                    continue;
                 }
+                let size = if is_dir {
+                    0
+                } else {
+                    entry.metadata().map_err(|e| e.to_string())?.len()
+                };
+                entries.push(PlannedEntry {
+                    source: path.to_path_buf(),
+                    name,
+                    is_dir,
+                    size,
+                    store: false,
+                    fixed_time: None,
+                });
             }
+            skipped_links.extend(cycles);
         } else {
             // 단일 파일인 경우
-            let name = src_path.file_name().unwrap().to_str().unwrap();
-            zip.start_file(name, options).map_err(|e| e.to_string())?;
-            let f = File::open(src_path).map_err(|e| e.to_string())?;
-            let mut reader = BufReader::new(f);
+            let name = src_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or("Invalid path")?
+                .to_string();
+            if !filter.is_included(&name, false) {
+                continue;
+            }
+            entries.push(PlannedEntry {
+                source: src_path.to_path_buf(),
+                name,
+                is_dir: false,
+                size: fs::metadata(src_path).map_err(|e| e.to_string())?.len(),
+                store: false,
+                fixed_time: None,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+// 압축 방식/레벨/암호화 옵션으로 FileOptions를 구성합니다.
+fn build_file_options<'k>(
+    method: Option<&str>,
+    password: Option<&'k str>,
+    encryption_mode: Option<&str>,
+    level: Option<i64>,
+) -> Result<FileOptions<'k, ()>, String> {
+    // "smart", "adaptive"는 기본 방식으로 deflate를 사용 (항목별 무압축 여부는 PlannedEntry::store)
+    let compression = match method.unwrap_or("deflated") {
+        "stored" => zip::CompressionMethod::Stored,
+        "zstd" => zip::CompressionMethod::Zstd,
+        "bzip2" => zip::CompressionMethod::Bzip2,
+        _ => zip::CompressionMethod::Deflated,
+    };
+    let level = resolve_compression_level(compression, level)?;
+
+    let mut options = FileOptions::<()>::default()
+        .compression_method(compression)
+        .compression_level(level)
+        .unix_permissions(0o755);
+
+    // 암호화 방식: 지정하지 않으면 기존과 동일하게 ZipCrypto 사용
+    if let Some(pass) = password {
+        options = match encryption_mode.unwrap_or("zipcrypto") {
+            "aes128" => options.with_aes_encryption(zip::AesMode::Aes128, pass),
+            "aes256" => options.with_aes_encryption(zip::AesMode::Aes256, pass),
+            "zipcrypto" => options.with_deprecated_encryption(pass.as_bytes()),
+            other => return Err(format!("Unknown encryption mode: {}", other)),
+        };
+    }
+    Ok(options)
+}
+
+#[derive(Default)]
+struct PauseState {
+    paused: bool,
+    // 지금까지 일시 정지된 총 시간 (현재 일시 정지 구간 제외)
+    total: Duration,
+    since: Option<Instant>,
+}
+
+// 작업 하나의 일시 정지 플래그
+// 작업 스레드는 읽기/쓰기 루프에서 wait()를 호출해, 일시 정지 중이면 재개될 때까지 Condvar로 대기합니다.
+// 취소 플래그가 연결되어 있으면 취소된 뒤에는 일시 정지 중이어도 바로 반환합니다.
+#[derive(Default)]
+struct PauseGate {
+    state: Mutex<PauseState>,
+    resumed: Condvar,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl PauseGate {
+    fn set_paused(&self, paused: bool) {
+        let mut state = self.state.lock().unwrap();
+        if paused && !state.paused {
+            state.since = Some(Instant::now());
+        } else if !paused {
+            if let Some(since) = state.since.take() {
+                state.total += since.elapsed();
+            }
+        }
+        state.paused = paused;
+        if !paused {
+            self.resumed.notify_all();
+        }
+    }
+
+    fn cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::SeqCst))
+    }
+
+    // 대기 중인 스레드가 취소 여부를 다시 확인하게 함 (잠금을 잡고 깨워야 확인 직후의 대기를 놓치지 않음)
+    fn wake(&self) {
+        let _state = self.state.lock().unwrap();
+        self.resumed.notify_all();
+    }
+
+    fn wait(&self) {
+        let mut state = self.state.lock().unwrap();
+        while state.paused && !self.cancelled() {
+            state = self.resumed.wait(state).unwrap();
+        }
+    }
+
+    // 누적 일시 정지 시간 (진행 중인 일시 정지 구간 포함)
+    fn paused_time(&self) -> Duration {
+        let state = self.state.lock().unwrap();
+        state.total + state.since.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
+// 진행 중인 작업들의 일시 정지 상태 (작업마다 자기 PauseGate를 가지므로,
+// 새 작업이 시작되거나 압축이 취소돼도 다른 작업의 일시 정지가 풀리지 않음)
+#[derive(Default)]
+struct PauseRegistry {
+    gates: Mutex<Vec<Arc<PauseGate>>>,
+}
+
+impl PauseRegistry {
+    // 새 작업의 일시 정지 플래그를 등록 (반환된 토큰이 drop되면 목록에서 빠짐)
+    // cancel을 넘기면 취소된 작업은 일시 정지 중이어도 wait()에서 깨어남
+    fn begin(&self, cancel: Option<&CancelToken>) -> PauseToken<'_> {
+        let gate = Arc::new(PauseGate {
+            cancel: cancel.map(|token| token.flag.clone()),
+            ..Default::default()
+        });
+        self.gates.lock().unwrap().push(gate.clone());
+        PauseToken {
+            registry: self,
+            gate,
+        }
+    }
+
+    // 진행 중인 모든 작업을 일시 정지하거나 재개
+    fn set_paused(&self, paused: bool) {
+        for gate in self.gates.lock().unwrap().iter() {
+            gate.set_paused(paused);
+        }
+    }
+
+    // 대기 중인 작업을 깨워 취소 여부를 다시 확인하게 함 (일시 정지 상태는 그대로 유지)
+    fn wake_all(&self) {
+        for gate in self.gates.lock().unwrap().iter() {
+            gate.wake();
+        }
+    }
+}
+
+struct PauseToken<'a> {
+    registry: &'a PauseRegistry,
+    gate: Arc<PauseGate>,
+}
+
+impl std::ops::Deref for PauseToken<'_> {
+    type Target = PauseGate;
+
+    fn deref(&self) -> &PauseGate {
+        &self.gate
+    }
+}
+
+impl Drop for PauseToken<'_> {
+    fn drop(&mut self) {
+        self.registry
+            .gates
+            .lock()
+            .unwrap()
+            .retain(|gate| !Arc::ptr_eq(gate, &self.gate));
+    }
+}
+
+// 진행 중인 압축 작업들의 취소 플래그 (작업마다 자기 플래그를 가지므로,
+// 새 작업이 시작돼도 다른 작업에 방금 들어온 취소 요청이 지워지지 않음)
+#[derive(Default)]
+struct CancelRegistry {
+    tokens: Mutex<Vec<Arc<AtomicBool>>>,
+}
+
+impl CancelRegistry {
+    // 새 작업의 취소 플래그를 등록 (반환된 토큰이 drop되면 목록에서 빠짐)
+    fn begin(&self) -> CancelToken<'_> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.tokens.lock().unwrap().push(flag.clone());
+        CancelToken {
+            registry: self,
+            flag,
+        }
+    }
+
+    // 진행 중인 모든 작업에 취소 요청
+    fn cancel_all(&self) {
+        for flag in self.tokens.lock().unwrap().iter() {
+            flag.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+struct CancelToken<'a> {
+    registry: &'a CancelRegistry,
+    flag: Arc<AtomicBool>,
+}
+
+impl std::ops::Deref for CancelToken<'_> {
+    type Target = AtomicBool;
+
+    fn deref(&self) -> &AtomicBool {
+        &self.flag
+    }
+}
+
+impl Drop for CancelToken<'_> {
+    fn drop(&mut self) {
+        self.registry
+            .tokens
+            .lock()
+            .unwrap()
+            .retain(|flag| !Arc::ptr_eq(flag, &self.flag));
+    }
+}
+
+// 속도 지수 이동 평균에서 최신 구간의 비중 (0.1초 단위 측정값의 흔들림 완화)
+const SPEED_SMOOTHING: f64 = 0.3;
+
+// 진행률 이벤트를 받을 창 (테스트의 MockRuntime 창도 같은 ProgressEmitter를 쓸 수 있도록 런타임을 감춤)
+trait ProgressSink: Sync {
+    fn send(&self, event: &str, payload: ProgressPayload) -> Result<(), String>;
+}
+
+impl<R: Runtime> ProgressSink for Window<R> {
+    fn send(&self, event: &str, payload: ProgressPayload) -> Result<(), String> {
+        self.emit(event, payload).map_err(|e| e.to_string())
+    }
+}
+
+// 진행률 이벤트를 0.1초 간격으로 전송합니다.
+// with_pause로 PauseGate를 연결하면 advance에서 일시 정지를 기다리고, 속도 계산에서 정지 시간을 뺍니다.
+struct ProgressEmitter<'a> {
+    window: &'a dyn ProgressSink,
+    event: &'static str,
+    total: u64,
+    processed: u64,
+    started: Instant,
+    last_emit: Instant,
+    // 마지막 전송 시점의 processed (구간 속도 계산용)
+    last_processed: u64,
+    // 평활화된 속도 (바이트/초)
+    speed: f64,
+    pause: Option<&'a PauseGate>,
+    // 마지막 전송 시점의 누적 일시 정지 시간
+    last_paused: Duration,
+    // 전체 크기를 모름 (처리한 바이트만 보고하고 남은 시간은 계산하지 않음)
+    indeterminate: bool,
+}
+
+impl<'a> ProgressEmitter<'a> {
+    fn new(window: &'a dyn ProgressSink, event: &'static str, total: u64) -> Self {
+        let now = Instant::now();
+        Self {
+            window,
+            event,
+            total,
+            processed: 0,
+            started: now,
+            last_emit: now,
+            last_processed: 0,
+            speed: 0.0,
+            pause: None,
+            last_paused: Duration::ZERO,
+            indeterminate: false,
+        }
+    }
+
+    // 전체 크기 없이 진행 상황을 보고 (total은 0으로 전송)
+    fn indeterminate(mut self) -> Self {
+        self.total = 0;
+        self.indeterminate = true;
+        self
+    }
+
+    fn with_pause(mut self, pause: &'a PauseGate) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    // 일시 정지 중이면 재개될 때까지 대기
+    fn wait_if_paused(&self) {
+        if let Some(pause) = self.pause {
+            pause.wait();
+        }
+    }
+
+    fn paused_time(&self) -> Duration {
+        self.pause.map(PauseGate::paused_time).unwrap_or_default()
+    }
+
+    fn advance(&mut self, n: u64, filename: &str) -> Result<(), String> {
+        self.wait_if_paused();
+        self.processed += n;
+        let paused = self.paused_time();
+        let elapsed = self
+            .last_emit
+            .elapsed()
+            .saturating_sub(paused.saturating_sub(self.last_paused));
+        if elapsed.as_millis() > 100 {
+            let sample = (self.processed - self.last_processed) as f64 / elapsed.as_secs_f64();
+            self.speed = if self.speed == 0.0 {
+                sample
+            } else {
+                SPEED_SMOOTHING * sample + (1.0 - SPEED_SMOOTHING) * self.speed
+            };
+            self.last_processed = self.processed;
+            self.emit(self.processed, filename, self.speed, None)?;
+            self.last_emit = Instant::now();
+            self.last_paused = paused;
+        }
+        Ok(())
+    }
+
+    // 완료 이벤트 전송 (속도는 일시 정지 시간을 뺀 전체 평균)
+    fn complete(&self, path: Option<String>) -> Result<(), String> {
+        let elapsed = self
+            .started
+            .elapsed()
+            .saturating_sub(self.paused_time())
+            .as_secs_f64();
+        let speed = if elapsed > 0.0 {
+            self.processed as f64 / elapsed
+        } else {
+            0.0
+        };
+        let processed = if self.indeterminate {
+            self.processed
+        } else {
+            self.total
+        };
+        self.emit(processed, "Complete", speed, path)
+    }
+
+    fn emit(
+        &self,
+        processed: u64,
+        filename: &str,
+        speed: f64,
+        path: Option<String>,
+    ) -> Result<(), String> {
+        let remaining = self.total.saturating_sub(processed);
+        let eta_secs = if self.indeterminate {
+            None
+        } else if remaining == 0 {
+            Some(0)
+        } else if speed > 0.0 {
+            Some((remaining as f64 / speed).ceil() as u64)
+        } else {
+            None
+        };
+        self.window.send(
+            self.event,
+            ProgressPayload {
+                total: self.total,
+                processed,
+                filename: filename.to_string(),
+                bytes_per_sec: speed as u64,
+                eta_secs,
+                path,
+            },
+        )
+    }
+}
+
+// 이 크기 이상의 파일은 ZIP64 헤더가 필요 (일반 헤더의 크기 필드는 32비트)
+const ZIP64_THRESHOLD: u64 = u32::MAX as u64;
+
+// 계획된 항목들을 ZIP에 씁니다. 취소 요청이 들어오면 CANCELLED 오류를 반환합니다.
+// 항목 이름은 항상 UTF-8로 기록되며, zip 크레이트가 ASCII가 아닌 이름(한글 등)에는
+// 범용 플래그의 UTF-8(언어 인코딩) 비트를 자동으로 설정하므로 다른 도구에서도 이름이 깨지지 않습니다.
+//
+// 4GB 이상의 파일은 large_file(true)로 ZIP64 로컬 헤더를 미리 예약합니다. (스트리밍 중에는 크기를
+// 다시 쓸 자리가 없으므로 미리 지정해야 함) 전체 ZIP이 4GB를 넘는 경우의 ZIP64 중앙 디렉터리는
+// zip 크레이트가 finish() 시 자동으로 기록하며, 해제 쪽(ZipArchive)도 ZIP64를 그대로 읽습니다.
+// 수동 테스트: `fsutil file createnew big.bin 5000000000`으로 5GB 희소 파일을 만들어 압축한 뒤
+// verify_zip / extract_zip으로 해제해 크기와 CRC가 일치하는지 확인합니다.
+// manifest를 넘기면 같은 읽기 루프에서 원본 내용의 SHA-256을 함께 계산해 기록합니다.
+fn write_entries<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    entries: &[PlannedEntry],
+    options: FileOptions<'_, ()>,
+    progress: &mut ProgressEmitter,
+    cancel: &AtomicBool,
+    buffer_size: usize,
+    mut manifest: Option<&mut Manifest>,
+) -> Result<(), String> {
+    let mut buffer = vec![0u8; buffer_size];
+
+    for entry in entries {
+        // 원본 수정 시간 보존
+        let entry_options = entry_file_options(entry, options);
+
+        if entry.is_dir {
+            zip.add_directory(entry.name.as_str(), entry_options)
+                .map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        let entry_options = entry_options.large_file(entry.size >= ZIP64_THRESHOLD);
+        zip.start_file(entry.name.as_str(), entry_options)
+            .map_err(|e| e.to_string())?;
+        let f = File::open(&entry.source).map_err(|e| e.to_string())?;
+        let mut reader = BufReader::new(f);
+        let mut hasher = manifest.is_some().then(Sha256::new);
+
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err("CANCELLED".to_string());
+            }
+            let n = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+            if n == 0 {
+                break;
+            }
+            zip.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&buffer[..n]);
+            }
+            progress.advance(n as u64, &entry.name)?;
+        }
+        if let (Some(manifest), Some(hasher)) = (manifest.as_deref_mut(), hasher) {
+            manifest.push(&entry.name, hasher);
+        }
+    }
+    Ok(())
+}
+
+// 이 크기 이하의 파일은 메모리에서 병렬로 압축한 뒤 raw_copy_file로 옮겨 담습니다.
+const PARALLEL_ENTRY_MAX_SIZE: u64 = 8 * 1024 * 1024;
+// 병렬 압축 한 묶음의 최대 원본 크기 (메모리 사용량 제한)
+const PARALLEL_BATCH_SIZE: u64 = 128 * 1024 * 1024;
+
+// write_entries의 병렬 버전 (작은 파일이 많은 경우 여러 코어를 사용)
+// 연속된 작은 파일들을 묶어 rayon으로 각각 메모리 ZIP에 압축하고, 원래 순서대로 복사합니다.
+// 폴더와 큰 파일은 기존처럼 스트리밍으로 씁니다.
+fn write_entries_parallel<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    entries: &[PlannedEntry],
+    options: FileOptions<'_, ()>,
+    progress: &mut ProgressEmitter,
+    cancel: &AtomicBool,
+    buffer_size: usize,
+    mut manifest: Option<&mut Manifest>,
+) -> Result<(), String> {
+    let is_small = |e: &PlannedEntry| !e.is_dir && e.size <= PARALLEL_ENTRY_MAX_SIZE;
+
+    let mut start = 0;
+    while start < entries.len() {
+        if !is_small(&entries[start]) {
+            write_entries(
+                zip,
+                &entries[start..start + 1],
+                options,
+                progress,
+                cancel,
+                buffer_size,
+                manifest.as_deref_mut(),
+            )?;
+            start += 1;
+            continue;
+        }
+
+        let mut end = start;
+        let mut batch_size = 0u64;
+        while end < entries.len()
+            && is_small(&entries[end])
+            && (end == start || batch_size + entries[end].size <= PARALLEL_BATCH_SIZE)
+        {
+            batch_size += entries[end].size;
+            end += 1;
+        }
+
+        // 진행률은 하나의 카운터를 잠금으로 공유해 스레드 간에도 단조 증가하도록 유지
+        let shared_progress = Mutex::new(&mut *progress);
+        let hash = manifest.is_some();
+        let compressed = entries[start..end]
+            .par_iter()
+            .map(|entry| {
+                compress_to_memory(entry, options, &shared_progress, cancel, buffer_size, hash)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        for (entry, (buffer, digest)) in entries[start..end].iter().zip(compressed) {
+            if let (Some(manifest), Some(digest)) = (manifest.as_deref_mut(), digest) {
+                manifest.push_hex(&entry.name, digest);
+            }
+            let mut archive = zip::ZipArchive::new(Cursor::new(buffer)).map_err(|e| e.to_string())?;
+            let file = archive.by_index_raw(0).map_err(|e| e.to_string())?;
+            zip.raw_copy_file(file).map_err(|e| e.to_string())?;
+        }
+        start = end;
+    }
+    Ok(())
+}
+
+// 파일 하나를 단일 항목 ZIP으로 메모리에 압축 (hash이면 원본 SHA-256도 함께 반환)
+fn compress_to_memory(
+    entry: &PlannedEntry,
+    options: FileOptions<'_, ()>,
+    progress: &Mutex<&mut ProgressEmitter>,
+    cancel: &AtomicBool,
+    buffer_size: usize,
+    hash: bool,
+) -> Result<(Vec<u8>, Option<String>), String> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let entry_options = entry_file_options(entry, options);
+    zip.start_file(entry.name.as_str(), entry_options)
+        .map_err(|e| e.to_string())?;
+
+    let f = File::open(&entry.source).map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(f);
+    let mut buffer = vec![0u8; buffer_size];
+    let mut read_total = 0u64;
+    let mut hasher = hash.then(Sha256::new);
+    // 진행률은 파일 단위로만 보고하므로 일시 정지는 읽기 루프에서 직접 확인
+    let pause = progress.lock().map_err(|e| e.to_string())?.pause;
+    loop {
+        if let Some(pause) = pause {
+            pause.wait();
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return Err("CANCELLED".to_string());
+        }
+        let n = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        zip.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
+        if let Some(hasher) = hasher.as_mut() {
+            hasher.update(&buffer[..n]);
+        }
+        read_total += n as u64;
+    }
+
+    progress
+        .lock()
+        .map_err(|e| e.to_string())?
+        .advance(read_total, &entry.name)?;
+    let digest = hasher.map(|h| manifest::to_hex(&h.finalize()));
+    Ok((zip.finish().map_err(|e| e.to_string())?.into_inner(), digest))
+}
+
+// 압축 결과 요약
+#[derive(serde::Serialize)]
+struct CompressSummary {
+    #[serde(rename = "inputBytes")]
+    input_bytes: u64,
+    #[serde(rename = "outputBytes")]
+    output_bytes: u64,
+    // 출력 크기 / 입력 크기 (0.55면 45% 절약)
+    ratio: f64,
+    #[serde(rename = "elapsedMs")]
+    elapsed_ms: u64,
+    #[serde(rename = "entryCount")]
+    entry_count: usize,
+    // dry_run일 때 만들어질 ZIP 내부 경로 목록
+    #[serde(skip_serializing_if = "Option::is_none")]
+    planned: Option<Vec<String>>,
+    // manifest 옵션으로 만든 SHA-256 목록 파일 경로
+    #[serde(rename = "manifestPath", skip_serializing_if = "Option::is_none")]
+    manifest_path: Option<String>,
+    // smart/adaptive 방식에서 무압축으로 저장한 파일 수와 압축한 파일 수
+    #[serde(rename = "storedCount", skip_serializing_if = "Option::is_none")]
+    stored_count: Option<usize>,
+    #[serde(rename = "compressedCount", skip_serializing_if = "Option::is_none")]
+    compressed_count: Option<usize>,
+    // min_file_size/max_file_size 조건에 맞지 않아 건너뛴 파일의 ZIP 내부 경로
+    #[serde(rename = "sizeSkipped", skip_serializing_if = "Vec::is_empty")]
+    size_skipped: Vec<String>,
+    // link_policy에 따라 건너뛴 심볼릭 링크/정션의 ZIP 내부 경로 (LinkPolicy 참고)
+    #[serde(rename = "skippedLinks", skip_serializing_if = "Vec::is_empty")]
+    skipped_links: Vec<String>,
+}
+
+// 압축 명령어
+// 진행 중 취소 요청을 받을 수 있도록 메인 스레드가 아닌 별도 스레드에서 실행합니다.
+// password를 지정하면 min_password_length(기본 1)보다 짧을 때 PASSWORD_TOO_SHORT 오류를 반환합니다.
+// method가 "smart"이면 store_extensions(기본값 DEFAULT_STORE_EXTENSIONS)에 해당하는 파일은
+// 무압축으로, 나머지는 deflate로 항목마다 방식을 골라 저장합니다.
+// method가 "adaptive"이면 확장자 대신 파일마다 앞부분 64KB를 압축해 보고, 이득이 5% 미만이면 무압축으로 저장합니다.
+// 두 방식 모두 무압축/압축한 파일 수를 결과의 storedCount/compressedCount로 반환합니다.
+// deterministic이면 같은 입력에서 항상 같은 바이트의 ZIP을 만듭니다 (빌드 파이프라인용).
+// 항목을 경로 순으로 정렬하고 모든 항목에 fixed_timestamp(unix 초, 기본 1980-01-01)를 기록하므로
+// 원본 수정 시간은 보존되지 않습니다. 암호화는 매번 무작위 값을 쓰므로 함께 사용할 수 없습니다.
+// manifest이면 압축하면서 항목마다 SHA-256을 계산해 <ZIP 경로>.sha256 파일로 함께 저장합니다 (manifest.rs 참고).
+// min_file_size/max_file_size(바이트)를 지정하면 그 범위 밖의 파일은 압축하지 않고 결과의 sizeSkipped에 담으며,
+// 진행률의 전체 크기에서도 뺍니다 (폴더는 크기 조건과 관계없이 포함).
+// base_prefix를 지정하면 모든 항목의 ZIP 내부 경로 앞에 붙입니다 (예: "backup" -> backup/<원래 경로>).
+// include/exclude 패턴은 prefix를 붙이기 전의 경로에 적용됩니다.
+// link_policy는 선택한 폴더 안의 심볼릭 링크/정션 처리 방식입니다 ("skip" 기본값 | "follow", LinkPolicy 참고).
+// 정션 하나로 드라이브 전체가 압축되는 일을 막기 위해 기본적으로 건너뛰며, 건너뛴 링크는 결과의 skippedLinks에 담습니다.
+// split_size(바이트, 최소 64KB)를 지정하면 <ZIP 경로>.001, .002, ... 조각으로 나누어 씁니다.
+// ZIP 분할 헤더가 없는 단순 바이트 분할이므로 조각을 순서대로 이어 붙이면 일반 ZIP이 되고, extract_zip은 아무 조각이나 받습니다.
+// <ZIP 경로>.part에 쓴 뒤 끝까지 성공했을 때만 이름을 바꾸므로, 대상 경로에는 완성된 ZIP만 생깁니다.
+#[tauri::command(async)]
+fn compress_files<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    paths: Vec<String>,
+    target_zip_path: String,
+    method: Option<String>,
+    password: Option<String>,
+    encryption_mode: Option<String>,
+    level: Option<i64>,
+    split_size: Option<u64>,
+    dry_run: Option<bool>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    comment: Option<String>,
+    buffer_size: Option<usize>,
+    store_extensions: Option<Vec<String>>,
+    min_password_length: Option<usize>,
+    deterministic: Option<bool>,
+    fixed_timestamp: Option<i64>,
+    manifest: Option<bool>,
+    min_file_size: Option<u64>,
+    max_file_size: Option<u64>,
+    base_prefix: Option<String>,
+    link_policy: Option<String>,
+) -> Result<CompressSummary, CommandError> {
+    let started = Instant::now();
+    let base_prefix = normalize_base_prefix(base_prefix.as_deref().unwrap_or_default())?;
+    let link_policy = link_policy
+        .as_deref()
+        .map(LinkPolicy::parse)
+        .transpose()?
+        .unwrap_or(LinkPolicy::Skip);
+    // 암호화를 요청했다면 빈 비밀번호나 min_password_length보다 짧은 비밀번호는 거부
+    if let Some(ref password) = password {
+        password::check_length(password, min_password_length.unwrap_or(1))?;
+    }
+    let buffer_size = resolve_buffer_size(buffer_size)?;
+    // 무압축(stored)은 CPU를 거의 쓰지 않으므로 병렬 압축의 이득이 없음
+    let parallel = method.as_deref() != Some("stored");
+    let options = build_file_options(
+        method.as_deref(),
+        password.as_deref(),
+        encryption_mode.as_deref(),
+        level,
+    )?;
+
+    // 이 작업의 취소/일시 정지 플래그 (다른 작업의 요청은 건드리지 않음)
+    let cancel = state.compress_cancel.begin();
+    let pause = state.pause.begin(Some(&cancel));
+
+    if let Some(size) = split_size {
+        if size < split::MIN_SPLIT_SIZE {
+            return Err(format!(
+                "Split size must be at least {} bytes",
+                split::MIN_SPLIT_SIZE
+            )
+            .into());
+        }
+    }
+
+    // 1. 전체 크기 계산 (진행률 표시용)
+    let filter = EntryFilter::new(
+        include.as_deref().unwrap_or_default(),
+        exclude.as_deref().unwrap_or_default(),
+    )?;
+    let mut skipped_links = Vec::new();
+    let mut entries = plan_entries(&paths, &filter, link_policy, &mut skipped_links)?;
+    if !base_prefix.is_empty() {
+        for entry in entries.iter_mut() {
+            entry.name.insert_str(0, &base_prefix);
+        }
+        for name in skipped_links.iter_mut() {
+            name.insert_str(0, &base_prefix);
+        }
+    }
+    let mut size_skipped = Vec::new();
+    if min_file_size.is_some() || max_file_size.is_some() {
+        entries.retain(|e| {
+            let keep = e.is_dir
+                || (min_file_size.map_or(true, |min| e.size >= min)
+                    && max_file_size.map_or(true, |max| e.size <= max));
+            if !keep {
+                size_skipped.push(e.name.clone());
+            }
+            keep
+        });
+    }
+    if method.as_deref() == Some("smart") {
+        let extensions = store_extensions.unwrap_or_else(|| {
+            DEFAULT_STORE_EXTENSIONS.iter().map(|e| e.to_string()).collect()
+        });
+        mark_stored_entries(&mut entries, &extensions);
+    }
+    if method.as_deref() == Some("adaptive") {
+        mark_incompressible_entries(&mut entries);
+    }
+    let (stored_count, compressed_count) = match method.as_deref() {
+        Some("smart" | "adaptive") => {
+            let files = entries.iter().filter(|e| !e.is_dir).count();
+            let stored = entries.iter().filter(|e| !e.is_dir && e.store).count();
+            (Some(stored), Some(files - stored))
+        }
+        _ => (None, None),
+    };
+    if deterministic.unwrap_or(false) {
+        if password.is_some() {
+            return Err("Deterministic output cannot be combined with encryption".into());
+        }
+        let fixed_time = fixed_zip_datetime(fixed_timestamp)?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        for entry in &mut entries {
+            entry.fixed_time = Some(fixed_time);
+        }
+    }
+    let total_size: u64 = entries.iter().map(|e| e.size).sum();
+
+    // dry_run이면 아무것도 쓰지 않고 계획된 항목만 반환
+    if dry_run.unwrap_or(false) {
+        return Ok(CompressSummary {
+            input_bytes: total_size,
+            output_bytes: 0,
+            ratio: 1.0,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            entry_count: entries.len(),
+            planned: Some(entries.into_iter().map(|e| e.name).collect()),
+            manifest_path: None,
+            stored_count,
+            compressed_count,
+            size_skipped,
+            skipped_links,
+        });
+    }
+
+    let path = Path::new(&target_zip_path);
+    let mut zip = zip::ZipWriter::new(SplitWriter::new(path, split_size));
+    let mut hashes = manifest.unwrap_or(false).then(Manifest::default);
+
+    let mut progress =
+        ProgressEmitter::new(&window, "compress-progress", total_size).with_pause(&pause);
+    let result = (|| -> Result<(), String> {
+        if parallel {
+            write_entries_parallel(
+                &mut zip,
+                &entries,
+                options,
+                &mut progress,
+                &cancel,
+                buffer_size,
+                hashes.as_mut(),
+            )?;
+        } else {
+            write_entries(
+                &mut zip,
+                &entries,
+                options,
+                &mut progress,
+                &cancel,
+                buffer_size,
+                hashes.as_mut(),
+            )?;
+        }
+
+        // 아카이브 전체 주석 (중앙 디렉터리 끝에 기록됨)
+        if let Some(comment) = comment {
+            zip.set_comment(comment);
+        }
+        Ok(())
+    })();
+
+    // ZipWriter는 drop될 때도 중앙 디렉터리를 기록하므로, 조각을 지우기 전에 먼저 drop
+    let result = match result {
+        Ok(()) => zip
+            .finish()
+            .map_err(|e| e.to_string())
+            .and_then(|mut writer| writer.seek(SeekFrom::End(0)).map_err(|e| e.to_string())),
+        Err(e) => {
+            drop(zip);
+            Err(e)
+        }
+    };
+    let result = result.and_then(|output_bytes| {
+        // 다 쓴 뒤에만 임시 파일(분할이면 모든 조각)을 최종 이름으로 변경
+        split::finish_parts(path, output_bytes, split_size).map_err(|e| e.to_string())?;
+        let manifest_path = match &hashes {
+            Some(hashes) => {
+                let manifest_path = manifest::path_for(path);
+                hashes.write(&manifest_path).map_err(|e| e.to_string())?;
+                Some(manifest_path.to_string_lossy().into_owned())
+            }
+            None => None,
+        };
+
+        // 완료 이벤트 전송 (해제 흐름과 동일한 형태, 분할이면 첫 조각 경로)
+        let output_path = match split_size {
+            Some(_) => split::part_path(path, 1).to_string_lossy().into_owned(),
+            None => target_zip_path.clone(),
+        };
+        progress.complete(Some(output_path))?;
+
+        Ok(CompressSummary {
+            input_bytes: total_size,
+            output_bytes,
+            ratio: if total_size > 0 {
+                output_bytes as f64 / total_size as f64
+            } else {
+                1.0
+            },
+            // 일시 정지된 시간은 소요 시간에서 제외
+            elapsed_ms: started.elapsed().saturating_sub(pause.paused_time()).as_millis() as u64,
+            entry_count: entries.len(),
+            planned: None,
+            manifest_path,
+            stored_count,
+            compressed_count,
+            size_skipped,
+            skipped_links,
+        })
+    });
+
+    // 취소/실패한 경우 불완전한 임시 파일(분할 조각 포함) 삭제
+    if result.is_err() {
+        split::remove_parts(path);
+    }
+    Ok(result?)
+}
+
+// compress_buffers로 압축할 (extract_to_memory로 읽은) 메모리 데이터 항목
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BufferEntry {
+    name: String,
+    bytes: Vec<u8>,
+}
+
+// 메모리 데이터 항목의 ZIP 내부 이름 ("\\"는 "/"로 바꾸고, 빈 이름과 ".." 경로는 거부)
+fn buffer_entry_name(name: &str) -> Result<String, String> {
+    let name = name.replace('\\', "/");
+    let name = name.trim_start_matches('/');
+    if name.is_empty() || name.split('/').any(|part| part == "..") {
+        return Err(format!("Invalid entry name: {}", name));
+    }
+    Ok(name.to_string())
+}
+
+// 메모리의 데이터를 ZIP으로 압축하는 명령어 (프론트엔드에서 만든 보고서/내보내기 파일을 임시 파일 없이 압축)
+// method, password, encryption_mode, level, comment, min_password_length는 compress_files와 같으며,
+// smart이면 DEFAULT_STORE_EXTENSIONS에 해당하는 이름의 항목만 무압축으로 저장합니다.
+// 항목 이름의 "/"는 폴더 구분자이며, 이름이 겹치면 DUPLICATE_ENTRY 오류를 반환합니다.
+// compress_files와 같이 <ZIP 경로>.part에 쓴 뒤 성공했을 때만 이름을 바꿉니다.
+#[tauri::command(async)]
+fn compress_buffers(
+    entries: Vec<BufferEntry>,
+    target_zip_path: String,
+    method: Option<String>,
+    password: Option<String>,
+    encryption_mode: Option<String>,
+    level: Option<i64>,
+    comment: Option<String>,
+    min_password_length: Option<usize>,
+) -> Result<CompressSummary, CommandError> {
+    let started = Instant::now();
+    if let Some(ref password) = password {
+        password::check_length(password, min_password_length.unwrap_or(1))?;
+    }
+    let options = build_file_options(
+        method.as_deref(),
+        password.as_deref(),
+        encryption_mode.as_deref(),
+        level,
+    )?;
+    let smart = method.as_deref() == Some("smart");
+
+    let mut names = std::collections::HashSet::new();
+    let mut planned = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let name = buffer_entry_name(&entry.name)?;
+        if !names.insert(name.clone()) {
+            return Err(format!("DUPLICATE_ENTRY: {}", name).into());
+        }
+        planned.push((name, entry.bytes));
+    }
+    let input_bytes: u64 = planned.iter().map(|(_, bytes)| bytes.len() as u64).sum();
+
+    let path = Path::new(&target_zip_path);
+    let mut zip = zip::ZipWriter::new(SplitWriter::new(path, None));
+    let result = (|| -> Result<(), String> {
+        for (name, bytes) in &planned {
+            let store = smart
+                && Path::new(name)
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_lowercase())
+                    .is_some_and(|ext| DEFAULT_STORE_EXTENSIONS.contains(&ext.as_str()));
+            let entry_options = if store {
+                options
+                    .compression_method(zip::CompressionMethod::Stored)
+                    .compression_level(None)
+            } else {
+                options
+            };
+            let entry_options = entry_options.large_file(bytes.len() as u64 >= ZIP64_THRESHOLD);
+            zip.start_file(name.as_str(), entry_options)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(bytes).map_err(|e| e.to_string())?;
+        }
+        if let Some(comment) = comment {
+            zip.set_comment(comment);
+        }
+        Ok(())
+    })();
+
+    // ZipWriter는 drop될 때도 중앙 디렉터리를 기록하므로, 임시 파일을 지우기 전에 먼저 drop
+    let result = match result {
+        Ok(()) => zip
+            .finish()
+            .map_err(|e| e.to_string())
+            .and_then(|mut writer| writer.seek(SeekFrom::End(0)).map_err(|e| e.to_string()))
+            .and_then(|output_bytes| {
+                split::finish_parts(path, output_bytes, None).map_err(|e| e.to_string())?;
+                Ok(output_bytes)
+            }),
+        Err(e) => {
+            drop(zip);
+            Err(e)
+        }
+    };
+    let output_bytes = match result {
+        Ok(output_bytes) => output_bytes,
+        Err(e) => {
+            split::remove_parts(path);
+            return Err(e.into());
+        }
+    };
+    Ok(CompressSummary {
+        input_bytes,
+        output_bytes,
+        ratio: if input_bytes > 0 {
+            output_bytes as f64 / input_bytes as f64
+        } else {
+            1.0
+        },
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        entry_count: planned.len(),
+        planned: None,
+        manifest_path: None,
+        stored_count: None,
+        compressed_count: None,
+        size_skipped: Vec::new(),
+        skipped_links: Vec::new(),
+    })
+}
+
+// 쓴 데이터를 버리고 위치와 크기만 기록하는 Writer (벤치마크용)
+// ZipWriter가 로컬 헤더를 다시 쓰려고 앞으로 돌아가는 Seek도 지원합니다.
+#[derive(Default)]
+struct DiscardWriter {
+    pos: u64,
+    len: u64,
+}
+
+impl Write for DiscardWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for DiscardWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(p) => Some(p),
+            SeekFrom::Current(d) => self.pos.checked_add_signed(d),
+            SeekFrom::End(d) => self.len.checked_add_signed(d),
+        };
+        self.pos = new_pos.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative position")
+        })?;
+        Ok(self.pos)
+    }
+}
+
+// 압축 벤치마크 결과
+#[derive(serde::Serialize)]
+struct BenchmarkResult {
+    #[serde(rename = "inputBytes")]
+    input_bytes: u64,
+    #[serde(rename = "outputBytes")]
+    output_bytes: u64,
+    // 출력 크기 / 입력 크기
+    ratio: f64,
+    #[serde(rename = "elapsedMs")]
+    elapsed_ms: u64,
+    // 초당 처리한 원본 크기 (1MB = 1024 * 1024 바이트)
+    #[serde(rename = "mbPerSec")]
+    mb_per_sec: f64,
+}
+
+// 압축 벤치마크 명령어 (방식/레벨별 속도와 압축률 비교용)
+// sample_path(파일 또는 폴더)를 compress_files와 같은 스트리밍 루프로 압축하되 출력은 버리므로 파일을 만들지 않습니다.
+// 디스크 쓰기 속도의 영향을 받지 않도록 병렬 압축 없이 한 스레드로 측정하며,
+// benchmark-progress 이벤트를 보내고 cancel_compression으로 취소할 수 있습니다.
+#[tauri::command(async)]
+fn benchmark_compression<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    sample_path: String,
+    method: Option<String>,
+    level: Option<i64>,
+) -> Result<BenchmarkResult, CommandError> {
+    let options = build_file_options(method.as_deref(), None, None, level)?;
+    let cancel = state.compress_cancel.begin();
+
+    let mut entries = plan_entries(
+        &[sample_path],
+        &EntryFilter::default(),
+        LinkPolicy::Skip,
+        &mut Vec::new(),
+    )?;
+    if method.as_deref() == Some("smart") {
+        let extensions: Vec<String> =
+            DEFAULT_STORE_EXTENSIONS.iter().map(|e| e.to_string()).collect();
+        mark_stored_entries(&mut entries, &extensions);
+    }
+    let input_bytes: u64 = entries.iter().map(|e| e.size).sum();
+
+    let started = Instant::now();
+    let mut progress = ProgressEmitter::new(&window, "benchmark-progress", input_bytes);
+    let mut zip = zip::ZipWriter::new(DiscardWriter::default());
+    write_entries(
+        &mut zip,
+        &entries,
+        options,
+        &mut progress,
+        &cancel,
+        DEFAULT_BUFFER_SIZE,
+        None,
+    )?;
+    let writer = zip.finish().map_err(|e| e.to_string())?;
+    let elapsed = started.elapsed();
+    progress.complete(None)?;
+
+    let secs = elapsed.as_secs_f64();
+    Ok(BenchmarkResult {
+        input_bytes,
+        output_bytes: writer.len,
+        ratio: if input_bytes > 0 {
+            writer.len as f64 / input_bytes as f64
+        } else {
+            1.0
+        },
+        elapsed_ms: elapsed.as_millis() as u64,
+        mb_per_sec: if secs > 0.0 {
+            input_bytes as f64 / (1024.0 * 1024.0) / secs
+        } else {
+            0.0
+        },
+    })
+}
+
+// 비밀번호 강도 점수 (0: 매우 약함 ~ 4: 매우 강함, 입력 중 실시간 표시용)
+#[tauri::command]
+fn estimate_password_strength(password: String) -> u8 {
+    password::estimate_strength(&password)
+}
+
+// 압축 취소 명령어
+#[tauri::command]
+fn cancel_compression(state: tauri::State<'_, AppState>) {
+    state.compress_cancel.cancel_all();
+    // 일시 정지 중인 작업도 깨워서 취소를 확인하게 함 (해제 작업의 일시 정지는 유지)
+    state.pause.wake_all();
+}
+
+// 진행 중인 압축/해제 작업 일시 정지 명령어
+// 작업은 다음 읽기/쓰기 단위에서 멈추며, 멈춘 동안에는 진행률 이벤트도 전송되지 않습니다.
+#[tauri::command]
+fn pause_operation(state: tauri::State<'_, AppState>) {
+    state.pause.set_paused(true);
+}
+
+// 일시 정지된 작업 재개 명령어
+#[tauri::command]
+fn resume_operation(state: tauri::State<'_, AppState>) {
+    state.pause.set_paused(false);
+}
+
+// 파일/폴더를 .tar.gz로 압축하는 명령어
+// compress_files와 같은 compress-progress 이벤트를 보내고 cancel_compression으로 취소할 수 있습니다.
+// level은 gzip 레벨(0-9, 기본값 6)이며, tar는 암호화를 지원하지 않습니다.
+// target_path가 이미 있으면 overwrite가 true일 때만 덮어쓰고, 아니면 FILE_EXISTS 오류를 반환합니다.
+// compress_files와 같이 <경로>.part에 쓴 뒤 성공했을 때만 이름을 바꾸므로, 실패해도 기존 파일은 그대로 남습니다.
+#[tauri::command(async)]
+fn create_tar_gz<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    paths: Vec<String>,
+    target_path: String,
+    level: Option<i64>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    overwrite: Option<bool>,
+) -> Result<CompressSummary, CommandError> {
+    let started = Instant::now();
+    let level = tarball::gzip_level(level)?;
+    let path = Path::new(&target_path);
+    if path.exists() && !overwrite.unwrap_or(false) {
+        return Err("FILE_EXISTS".into());
+    }
+    let cancel = state.compress_cancel.begin();
+
+    let filter = EntryFilter::new(
+        include.as_deref().unwrap_or_default(),
+        exclude.as_deref().unwrap_or_default(),
+    )?;
+    let entries = plan_entries(&paths, &filter, LinkPolicy::Skip, &mut Vec::new())?;
+    let total_size: u64 = entries.iter().map(|e| e.size).sum();
+
+    let temp_path = split::temp_path(path);
+    let mut progress = ProgressEmitter::new(&window, "compress-progress", total_size);
+    let result = tarball::create_tar_gz(&temp_path, &entries, level, &mut progress, &cancel);
+    let output_bytes = replace_with_temp(result, &temp_path, path)?;
+    progress.complete(Some(target_path.clone()))?;
+
+    Ok(CompressSummary {
+        input_bytes: total_size,
+        output_bytes,
+        ratio: if total_size > 0 {
+            output_bytes as f64 / total_size as f64
+        } else {
+            1.0
+        },
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        entry_count: entries.len(),
+        planned: None,
+        manifest_path: None,
+        stored_count: None,
+        compressed_count: None,
+        size_skipped: Vec::new(),
+        skipped_links: Vec::new(),
+    })
+}
+
+// 단일 파일을 .gz로 압축하는 명령어
+// compress_files와 같은 compress-progress 이벤트를 보내고 cancel_compression으로 취소할 수 있습니다.
+// dest가 이미 있으면 overwrite가 true일 때만 덮어쓰고, 아니면 FILE_EXISTS 오류를 반환합니다.
+// <dest>.part에 쓴 뒤 성공했을 때만 이름을 바꾸므로, 실패하거나 취소돼도 기존 dest는 그대로 남습니다.
+#[tauri::command(async)]
+fn gzip_file<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    src: String,
+    dest: String,
+    level: Option<i64>,
+    overwrite: Option<bool>,
+) -> Result<u64, CommandError> {
+    let level = tarball::gzip_level(level)?;
+    let (src_path, dest_path) = (Path::new(&src), Path::new(&dest));
+    if src_path == dest_path {
+        return Err("Destination must differ from the source file".into());
+    }
+    if dest_path.exists() && !overwrite.unwrap_or(false) {
+        return Err("FILE_EXISTS".into());
+    }
+    let cancel = state.compress_cancel.begin();
+
+    let total_size = fs::metadata(src_path).map_err(|e| e.to_string())?.len();
+    let mut progress = ProgressEmitter::new(&window, "compress-progress", total_size);
+    let temp_path = split::temp_path(dest_path);
+    let result = tarball::gzip_file(src_path, &temp_path, level, &mut progress, &cancel);
+    let size = replace_with_temp(result, &temp_path, dest_path)?;
+    progress.complete(Some(dest.clone()))?;
+    Ok(size)
+}
+
+// 임시 파일에 쓰기가 성공했으면 dest로 이름을 바꾸고, 실패했으면 임시 파일만 삭제 (기존 dest는 건드리지 않음)
+fn replace_with_temp(
+    result: Result<u64, String>,
+    temp_path: &Path,
+    dest: &Path,
+) -> Result<u64, String> {
+    let result = result.and_then(|size| {
+        fs::rename(temp_path, dest)
+            .map(|_| size)
+            .map_err(|e| e.to_string())
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(temp_path);
+    }
+    result
+}
+
+// .gz 파일을 해제하는 명령어 (extract-progress 이벤트 전송)
+// dest 처리 규칙은 gzip_file과 같습니다 (<dest>.part에 쓴 뒤 성공했을 때만 이름을 바꿈).
+#[tauri::command(async)]
+fn gunzip_file<R: Runtime>(
+    window: Window<R>,
+    src: String,
+    dest: String,
+    overwrite: Option<bool>,
+) -> Result<u64, CommandError> {
+    let (src_path, dest_path) = (Path::new(&src), Path::new(&dest));
+    if src_path == dest_path {
+        return Err("Destination must differ from the source file".into());
+    }
+    if dest_path.exists() && !overwrite.unwrap_or(false) {
+        return Err("FILE_EXISTS".into());
+    }
+
+    let total_size = fs::metadata(src_path).map_err(|e| e.to_string())?.len();
+    let mut progress = ProgressEmitter::new(&window, "extract-progress", total_size);
+    let temp_path = split::temp_path(dest_path);
+    let result = tarball::gunzip_file(src_path, &temp_path, &mut progress);
+    let size = replace_with_temp(result, &temp_path, dest_path)?;
+    progress.complete(Some(dest.clone()))?;
+    Ok(size)
+}
+
+// ZIP을 자동 압축 해제 실행 파일(.exe)로 변환하는 명령어
+// 스텁 실행 파일(sfx_stub.exe) 뒤에 ZIP을 붙이고 오프셋을 보정합니다. 자세한 내용은 sfx.rs 참고.
+// 비밀번호로 암호화된 ZIP도 그대로 사용할 수 있으며, 실행 시 스텁이 비밀번호를 입력받습니다.
+// output이 이미 있으면 overwrite가 true일 때만 교체하며, 변환에 실패하면 기존 output은 그대로 남습니다.
+#[tauri::command(async)]
+fn create_sfx(
+    app: AppHandle,
+    zip_path: String,
+    output_path: String,
+    overwrite: Option<bool>,
+) -> Result<u64, CommandError> {
+    let output = Path::new(&output_path);
+    if output == Path::new(&zip_path) {
+        return Err("Output path must differ from the source ZIP".into());
+    }
+    if output.exists() && !overwrite.unwrap_or(false) {
+        return Err("FILE_EXISTS".into());
+    }
+    let stub = sfx::find_stub(app.path().resource_dir().ok())
+        .ok_or_else(|| format!("SFX stub not found: {}", sfx::STUB_FILE_NAME))?;
+
+    Ok(sfx::create(&stub, Path::new(&zip_path), output)?)
+}
+
+// 기존 ZIP에 파일을 추가하는 명령어
+// on_conflict: "skip"이면 같은 이름의 항목은 건너뛰고, "overwrite"이면 기존 항목을 교체합니다.
+// 지정하지 않았는데 이름이 겹치면 FILE_EXISTS 오류를 반환합니다. 이미 있는 폴더는 정책과 관계없이 그대로 둡니다.
+#[tauri::command(async)]
+fn append_to_zip<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    zip_path: String,
+    paths: Vec<String>,
+    on_conflict: Option<String>,
+    method: Option<String>,
+    password: Option<String>,
+    encryption_mode: Option<String>,
+    level: Option<i64>,
+) -> Result<(), CommandError> {
+    let path = Path::new(&zip_path);
+    let mut archive =
+        zip::ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+
+    // 압축 방식을 지정하지 않으면 기존 항목과 같은 방식을 사용
+    let method = match method {
+        Some(m) => Some(m),
+        None => {
+            let mut existing = None;
+            for i in 0..archive.len() {
+                let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+                if !file.is_dir() {
+                    existing = Some(match file.compression() {
+                        zip::CompressionMethod::Stored => "stored".to_string(),
+                        zip::CompressionMethod::Zstd => "zstd".to_string(),
+                        zip::CompressionMethod::Bzip2 => "bzip2".to_string(),
+                        _ => "deflated".to_string(),
+                    });
+                    break;
+                }
+            }
+            existing
+        }
+    };
+    let options = build_file_options(
+        method.as_deref(),
+        password.as_deref(),
+        encryption_mode.as_deref(),
+        level,
+    )?;
+
+    let existing_names: std::collections::HashSet<String> =
+        archive.file_names().map(|s| s.to_string()).collect();
+    let mut entries = plan_entries(
+        &paths,
+        &EntryFilter::default(),
+        LinkPolicy::Skip,
+        &mut Vec::new(),
+    )?;
+    // 폴더 항목은 ZIP에 "이름/"으로 기록되고 내용이 없으므로, 이미 있는 폴더는 충돌로 보지 않고 건너뜀
+    entries.retain(|e| !(e.is_dir && existing_names.contains(&format!("{}/", e.name))));
+    let has_conflict = entries.iter().any(|e| existing_names.contains(&e.name));
+
+    let overwrite = match on_conflict.as_deref() {
+        _ if !has_conflict => false,
+        Some("skip") => {
+            entries.retain(|e| !existing_names.contains(&e.name));
+            false
+        }
+        Some("overwrite") => true,
+        Some(other) => return Err(format!("Unknown conflict policy: {}", other).into()),
+        None => return Err("FILE_EXISTS".into()),
+    };
+
+    let cancel = state.compress_cancel.begin();
+
+    let total_size = entries.iter().map(|e| e.size).sum();
+    let mut progress = ProgressEmitter::new(&window, "compress-progress", total_size);
+
+    if overwrite {
+        // ZIP은 항목 삭제가 불가능하므로, 교체되지 않는 기존 항목을 그대로(raw) 복사한 새 파일을 만든 뒤 원본과 바꿉니다.
+        let replaced: std::collections::HashSet<&str> =
+            entries.iter().map(|e| e.name.as_str()).collect();
+        let temp_path = path.with_extension("zip.tmp");
+        let mut zip =
+            zip::ZipWriter::new(File::create(&temp_path).map_err(|e| e.to_string())?);
+
+        let result = (|| -> Result<(), String> {
+            for i in 0..archive.len() {
+                let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+                if replaced.contains(file.name()) {
+                    continue;
+                }
+                zip.raw_copy_file(file).map_err(|e| e.to_string())?;
+            }
+            write_entries(
+                &mut zip,
+                &entries,
+                options,
+                &mut progress,
+                &cancel,
+                DEFAULT_BUFFER_SIZE,
+                None,
+            )?;
+            if let Ok(comment) = std::str::from_utf8(archive.comment()) {
+                zip.set_comment(comment);
+            }
+            Ok(())
+        })();
+
+        // ZipWriter는 drop될 때도 중앙 디렉터리를 기록하므로, 임시 파일을 지우기 전에 먼저 drop
+        let result = match result {
+            Ok(()) => zip.finish().map(|_| ()).map_err(|e| e.to_string()),
+            Err(e) => {
+                drop(zip);
+                Err(e)
+            }
+        };
+        if let Err(e) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+        drop(archive);
+        fs::rename(&temp_path, path).map_err(|e| e.to_string())?;
+    } else {
+        drop(archive);
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| e.to_string())?;
+        let mut zip = zip::ZipWriter::new_append(file).map_err(|e| e.to_string())?;
+
+        // 실패/취소 시 작성 중이던 항목을 제거하고 기존 항목을 유지한 채 마무리
+        if let Err(e) = write_entries(
+            &mut zip,
+            &entries,
+            options,
+            &mut progress,
+            &cancel,
+            DEFAULT_BUFFER_SIZE,
+            None,
+        ) {
+            let _ = zip.abort_file();
+            let _ = zip.finish();
+            return Err(e.into());
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+    }
+
+    Ok(progress.complete(Some(zip_path.clone()))?)
+}
+
+// ZIP에서 항목을 삭제하는 명령어
+// 재압축 없이 남길 항목만 새 임시 ZIP으로 복사(raw copy)한 뒤 원본과 교체합니다.
+// "docs/"처럼 폴더 접두사를 주면 그 아래 항목이 모두 삭제되며, 삭제된 항목 수를 반환합니다.
+#[tauri::command(async)]
+fn remove_from_zip(zip_path: String, names: Vec<String>) -> Result<usize, CommandError> {
+    let path = Path::new(&zip_path);
+    let mut archive =
+        zip::ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+
+    let temp_path = path.with_extension("zip.tmp");
+    let mut zip = zip::ZipWriter::new(File::create(&temp_path).map_err(|e| e.to_string())?);
+
+    let result = (|| -> Result<usize, String> {
+        let mut removed = 0;
+        for i in 0..archive.len() {
+            let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            if names.iter().any(|n| entry_matches(n, file.name())) {
+                removed += 1;
+                continue;
+            }
+            zip.raw_copy_file(file).map_err(|e| e.to_string())?;
+        }
+        if removed == 0 {
+            return Err("No matching entries found".to_string());
+        }
+        if let Ok(comment) = std::str::from_utf8(archive.comment()) {
+            zip.set_comment(comment);
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok(removed)
+    })();
+
+    let removed = match result {
+        Ok(removed) => removed,
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+    };
+    drop(archive);
+    fs::rename(&temp_path, path).map_err(|e| e.to_string())?;
+    Ok(removed)
+}
+
+// old_name을 new_name으로 바꿨을 때의 항목 이름 (해당하지 않으면 None)
+// old_name이 폴더이면 그 아래 항목은 앞쪽 경로만 바꿉니다.
+fn renamed_entry(name: &str, old_name: &str, new_name: &str) -> Option<String> {
+    if name == old_name {
+        return Some(new_name.to_string());
+    }
+    let old_prefix = old_name.trim_end_matches('/');
+    let rest = name.strip_prefix(old_prefix)?.strip_prefix('/')?;
+    Some(format!("{}/{}", new_name.trim_end_matches('/'), rest))
+}
+
+// ZIP 항목의 이름을 바꾸는 명령어
+// remove_from_zip과 같이 모든 항목을 새 임시 ZIP으로 복사(raw copy)하면서 일치하는 항목만 새 이름으로 기록한 뒤 원본과 교체합니다.
+// 다시 압축하지 않으므로 압축 방식과 암호화가 그대로 유지되며 비밀번호가 필요 없습니다.
+// old_name이 폴더("docs/" 또는 "docs")이면 그 아래 항목도 함께 옮겨지며, 바뀐 항목 수를 반환합니다.
+// 새 이름이 바뀌지 않는 기존 항목과 겹치면 FILE_EXISTS 오류를 반환합니다.
+// 새 이름에 상위 경로(..), 절대 경로, 드라이브 접두사가 있으면 풀 때 대상 폴더를 벗어나므로 PATH_TRAVERSAL 오류를 반환합니다.
+#[tauri::command(async)]
+fn rename_zip_entry(
+    zip_path: String,
+    old_name: String,
+    new_name: String,
+) -> Result<usize, CommandError> {
+    let mut new_name = new_name.replace('\\', "/");
+    if new_name.trim_matches('/').is_empty() {
+        return Err("New name is empty".into());
+    }
+    if new_name.starts_with('/')
+        || has_drive_prefix(&new_name)
+        || new_name.split('/').any(|part| part == "..")
+    {
+        return Err(format!("PATH_TRAVERSAL: {}", new_name).into());
+    }
+    // 폴더 항목은 이름 끝의 '/'로 구분되므로 유지
+    if old_name.ends_with('/') && !new_name.ends_with('/') {
+        new_name.push('/');
+    }
+
+    let path = Path::new(&zip_path);
+    let mut archive =
+        zip::ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+
+    let mut kept = std::collections::HashSet::new();
+    let mut renamed = Vec::new();
+    for name in archive.file_names() {
+        match renamed_entry(name, &old_name, &new_name) {
+            Some(new) => renamed.push(new),
+            None => {
+                kept.insert(name.to_string());
+            }
+        }
+    }
+    if renamed.is_empty() {
+        return Err(format!("Entry not found: {}", old_name).into());
+    }
+    if renamed.iter().any(|name| kept.contains(name)) {
+        return Err("FILE_EXISTS".into());
+    }
+
+    let temp_path = path.with_extension("zip.tmp");
+    let mut zip = zip::ZipWriter::new(File::create(&temp_path).map_err(|e| e.to_string())?);
+
+    let result = (|| -> Result<(), String> {
+        for i in 0..archive.len() {
+            let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            let copied = match renamed_entry(file.name(), &old_name, &new_name) {
+                Some(new) => zip.raw_copy_file_rename(file, new),
+                None => zip.raw_copy_file(file),
+            };
+            copied.map_err(|e| e.to_string())?;
+        }
+        if let Ok(comment) = std::str::from_utf8(archive.comment()) {
+            zip.set_comment(comment);
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+    drop(archive);
+    fs::rename(&temp_path, path).map_err(|e| e.to_string())?;
+    Ok(renamed.len())
+}
+
+// 기존 ZIP의 SHA-256 목록 파일(<ZIP 경로>.sha256)을 만드는 명령어
+// compress_files의 manifest 옵션과 같은 형식이며, 항목을 풀어 읽으므로 암호화된 ZIP은 password가 필요합니다.
+// 기록한 항목 수를 반환합니다.
+#[tauri::command(async)]
+fn generate_manifest<R: Runtime>(
+    window: Window<R>,
+    zip_path: String,
+    password: Option<String>,
+) -> Result<usize, CommandError> {
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut total_size = 0;
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if !file.is_dir() {
+            total_size += file.size();
+        }
+    }
+
+    let mut progress = ProgressEmitter::new(&window, "verify-progress", total_size);
+    let mut hashes = Manifest::default();
+    let mut count = 0;
+    let mut buffer = vec![0u8; DEFAULT_BUFFER_SIZE];
+    for i in 0..archive.len() {
+        let mut file = if let Some(ref p) = password {
+            archive
+                .by_index_decrypt(i, p.as_bytes())
+                .map_err(|e| e.to_string())?
+        } else {
+            archive.by_index(i).map_err(|e| e.to_string())?
+        };
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut hasher = Sha256::new();
+        loop {
+            let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            progress.advance(n as u64, &name)?;
+        }
+        hashes.push(&name, hasher);
+        count += 1;
+    }
+
+    let manifest_path = manifest::path_for(Path::new(&zip_path));
+    hashes.write(&manifest_path)?;
+    progress.complete(Some(manifest_path.to_string_lossy().into_owned()))?;
+    Ok(count)
+}
+
+#[derive(serde::Serialize)]
+struct RecompressSummary {
+    // 원본/결과 ZIP 파일 크기
+    #[serde(rename = "inputBytes")]
+    input_bytes: u64,
+    #[serde(rename = "outputBytes")]
+    output_bytes: u64,
+    #[serde(rename = "entryCount")]
+    entry_count: usize,
+}
+
+// ZIP의 압축 방식을 바꾸는 명령어 (예: stored 아카이브를 deflated/zstd로 줄이거나, 반대로 빠르게 풀리도록 변경)
+// 모든 항목을 풀어 새 방식으로 다시 압축하며, 항목 이름/수정 시간/폴더 구조와 아카이브 주석은 그대로 유지합니다.
+// password는 원본 복호화용이고, output_password를 지정하면 encryption_mode 방식으로 다시 암호화합니다.
+// dest가 이미 있으면 overwrite가 true일 때만 교체하며, 실패하거나 취소돼도 기존 dest는 그대로 남습니다.
+#[tauri::command(async)]
+fn recompress_zip<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    src: String,
+    dest: String,
+    method: Option<String>,
+    level: Option<i64>,
+    password: Option<String>,
+    output_password: Option<String>,
+    encryption_mode: Option<String>,
+    overwrite: Option<bool>,
+) -> Result<RecompressSummary, CommandError> {
+    let dest_path = Path::new(&dest);
+    if dest_path == Path::new(&src) {
+        return Err("Output path must differ from the source ZIP".into());
+    }
+    if dest_path.exists() && !overwrite.unwrap_or(false) {
+        return Err("FILE_EXISTS".into());
+    }
+    let options = build_file_options(
+        method.as_deref(),
+        output_password.as_deref(),
+        encryption_mode.as_deref(),
+        level,
+    )?;
+
+    let file = File::open(&src).map_err(|e| e.to_string())?;
+    let input_bytes = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut total_size = 0;
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if !file.is_dir() {
+            total_size += file.size();
+        }
+    }
+
+    let cancel = state.compress_cancel.begin();
+    let pause = state.pause.begin(Some(&cancel));
+    let mut progress =
+        ProgressEmitter::new(&window, "compress-progress", total_size).with_pause(&pause);
+    let mut zip = zip::ZipWriter::new(SplitWriter::new(dest_path, None));
+
+    let result = (|| -> Result<(), String> {
+        let mut buffer = vec![0u8; DEFAULT_BUFFER_SIZE];
+        for i in 0..archive.len() {
+            let mut file = if let Some(ref p) = password {
+                archive
+                    .by_index_decrypt(i, p.as_bytes())
+                    .map_err(|e| e.to_string())?
+            } else {
+                archive.by_index(i).map_err(|e| e.to_string())?
+            };
+            let name = file.name().to_string();
+
+            // 원본 수정 시간과 권한 보존
+            let mut entry_options = options;
+            if let Some(time) = file.last_modified() {
+                entry_options = entry_options.last_modified_time(time);
+            }
+            if let Some(mode) = file.unix_mode() {
+                entry_options = entry_options.unix_permissions(mode);
+            }
+
+            if file.is_dir() {
+                zip.add_directory(name.as_str(), entry_options)
+                    .map_err(|e| e.to_string())?;
+                continue;
+            }
+
+            let entry_options = entry_options.large_file(file.size() >= ZIP64_THRESHOLD);
+            zip.start_file(name.as_str(), entry_options)
+                .map_err(|e| e.to_string())?;
+            loop {
+                if cancel.load(Ordering::Relaxed) {
+                    return Err("CANCELLED".to_string());
+                }
+                let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
+                if n == 0 {
+                    break;
+                }
+                zip.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
+                progress.advance(n as u64, &name)?;
+            }
+        }
+        if let Ok(comment) = std::str::from_utf8(archive.comment()) {
+            zip.set_comment(comment);
+        }
+        Ok(())
+    })();
+
+    // compress_files와 같이 <dest>.part에 쓴 뒤 성공했을 때만 이름을 바꾸므로, 실패해도 기존 dest는 그대로 남음
+    // ZipWriter는 drop될 때도 중앙 디렉터리를 기록하므로, 임시 파일을 지우기 전에 먼저 drop
+    let result = match result {
+        Ok(()) => zip
+            .finish()
+            .map_err(|e| e.to_string())
+            .and_then(|mut writer| writer.seek(SeekFrom::End(0)).map_err(|e| e.to_string()))
+            .and_then(|output_bytes| {
+                split::finish_parts(dest_path, output_bytes, None).map_err(|e| e.to_string())?;
+                Ok(output_bytes)
+            }),
+        Err(e) => {
+            drop(zip);
+            Err(e)
+        }
+    };
+    let output_bytes = match result {
+        Ok(size) => size,
+        Err(e) => {
+            split::remove_parts(dest_path);
+            return Err(e.into());
+        }
+    };
+    progress.complete(Some(dest.clone()))?;
+    Ok(RecompressSummary {
+        input_bytes,
+        output_bytes,
+        entry_count: archive.len(),
+    })
+}
+
+// into_subfolder일 때 아카이브 이름으로 만든 해제 폴더 (report.zip -> <target_dir>/report, a.tar.gz -> a)
+// 같은 이름의 폴더가 이미 있으면 rename일 때만 "report (1)"처럼 새 폴더를 쓰고, 아니면 기존 폴더에 풉니다.
+// 같은 이름의 파일이 있으면 항상 번호를 붙인 폴더를 씁니다.
+fn extract_target_dir(
+    target_dir: &str,
+    zip_path: &str,
+    into_subfolder: bool,
+    rename: bool,
+) -> PathBuf {
+    let target = PathBuf::from(target_dir);
+    if !into_subfolder {
+        return target;
+    }
+    let stem = Path::new(zip_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // .tar.gz는 file_stem이 "a.tar"이므로 .tar도 제거
+    let stem = match stem.len().checked_sub(4) {
+        Some(cut) if stem.is_char_boundary(cut) && stem[cut..].eq_ignore_ascii_case(".tar") => {
+            stem[..cut].to_string()
+        }
+        _ => stem,
+    };
+    let folder = target.join(if stem.is_empty() { "extracted" } else { &stem });
+    if folder.is_file() || (rename && folder.exists()) {
+        unique_path(&folder)
+    } else {
+        folder
+    }
+}
+
+// extract_nested에서 max_nested_depth를 생략했을 때 풀어 볼 중첩 깊이
+const DEFAULT_NESTED_DEPTH: u32 = 3;
+
+// 아카이브 하나를 풀 때의 공통 옵션 (중첩 아카이브에도 같은 값을 사용)
+struct UnpackOptions<'a> {
+    password: Option<&'a str>,
+    encoding: Option<&'static encoding_rs::Encoding>,
+    symlink_policy: SymlinkPolicy,
+    name_substitute: char,
+}
+
+// 해제한 중첩 아카이브 (nested는 그 안에서 다시 해제한 아카이브)
+#[derive(serde::Serialize)]
+struct NestedArchive {
+    path: String,
+    #[serde(rename = "extractedTo")]
+    extracted_to: String,
+    nested: Vec<NestedArchive>,
+    // 비밀번호가 다르거나 손상되어 풀지 못했을 때의 오류
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+// 중첩 해제 대상인 아카이브인지 확장자로 판별 (.zip, .tar, .tar.gz, .tgz)
+fn is_nested_archive(path: &Path) -> bool {
+    tarball::detect(path).is_some()
+        || path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+// 아카이브 하나를 target_dir에 풀고 해제한 파일 경로 목록을 반환합니다.
+// .tar, .tar.gz, .tgz는 tar로, 나머지는 ZIP으로 풉니다.
+fn unpack_archive<R: Runtime>(
+    window: &Window<R>,
+    archive_path: &Path,
+    target_dir: &Path,
+    options: &UnpackOptions,
+    guard: &mut BombGuard,
+    summary: &mut ExtractSummary,
+) -> Result<Vec<PathBuf>, String> {
+    if let Some(kind) = tarball::detect(archive_path) {
+        let total_size = fs::metadata(archive_path).map_err(|e| e.to_string())?.len();
+        let mut progress = ProgressEmitter::new(window, "extract-progress", total_size);
+        return tarball::extract(archive_path, kind, target_dir, &mut progress, guard);
+    }
+
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let declared = (0..archive.len())
+        .map(|i| {
+            let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            Ok((raw.size(), raw.compressed_size()))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    guard.check_declared(declared)?;
+    fs::create_dir_all(target_dir).map_err(|e| e.to_string())?;
+    let names = entry_names(&mut archive, options.encoding)?;
+    let mut extracted = Vec::new();
+
+    for i in 0..archive.len() {
+        // by_index는 암호화된 항목에서 바로 실패하므로 암호화 여부는 raw 항목으로 확인
+        let is_encrypted = archive
+            .by_index_raw(i)
+            .map_err(|e| e.to_string())?
+            .encrypted();
+
+        let mut file = if is_encrypted {
+            match options.password {
+                Some(p) => archive
+                    .by_index_decrypt(i, p.as_bytes())
+                    .map_err(|e| e.to_string())?,
+                None => return Err("Password required".into()),
+            }
+        } else {
+            archive.by_index(i).map_err(|e| e.to_string())?
+        };
+
+        let output_name = sanitize_entry_name(&names[i], options.name_substitute);
+        let outpath = safe_output_path(target_dir, &output_name)?;
+        if let std::borrow::Cow::Owned(_) = output_name {
+            summary.sanitized.push(RenamedEntry {
+                name: names[i].clone(),
+                path: outpath.to_string_lossy().into_owned(),
+            });
+        }
+
+        if (*file.name()).ends_with('/') {
+            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
+        } else if is_symlink_mode(file.unix_mode()) {
+            let link = extract_symlink(
+                &mut file,
+                &names[i],
+                &outpath,
+                target_dir,
+                options.symlink_policy,
+            )?;
+            summary.symlinks.push(link);
+        } else {
+            if let Some(p) = outpath.parent() {
+                if !p.exists() {
+                    fs::create_dir_all(p).map_err(|e| e.to_string())?;
+                }
+            }
+            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
+            io::copy(&mut GuardedReader::new(&mut file, guard), &mut outfile)
+                .map_err(|e| e.to_string())?;
+            restore_modified_time(&outfile, file.last_modified());
+            extracted.push(outpath);
+        }
+    }
+    Ok(extracted)
+}
+
+// 해제한 파일 중 아카이브를 찾아 "<이름> 폴더"에 다시 풀고, 그 결과에서도 depth까지 반복합니다.
+// 원본 아카이브 파일은 그대로 둡니다. 크기 제한은 바깥 아카이브와 같은 guard로 합산하므로
+// 여러 겹으로 감싼 압축 폭탄도 ZIP_BOMB 오류로 중단됩니다. 그 밖의 오류는 해당 항목의 error에 담고 계속합니다.
+fn extract_nested_archives<R: Runtime>(
+    window: &Window<R>,
+    files: &[PathBuf],
+    depth: u32,
+    options: &UnpackOptions,
+    guard: &mut BombGuard,
+    summary: &mut ExtractSummary,
+) -> Result<Vec<NestedArchive>, String> {
+    let mut tree = Vec::new();
+    if depth == 0 {
+        return Ok(tree);
+    }
+    for path in files.iter().filter(|p| is_nested_archive(p)) {
+        let parent = path.parent().unwrap_or(Path::new(""));
+        let target = extract_target_dir(
+            &parent.to_string_lossy(),
+            &path.to_string_lossy(),
+            true,
+            true,
+        );
+        let mut node = NestedArchive {
+            path: path.to_string_lossy().into_owned(),
+            extracted_to: target.to_string_lossy().into_owned(),
+            nested: Vec::new(),
+            error: None,
+        };
+        match unpack_archive(window, path, &target, options, guard, summary) {
+            Ok(inner) => {
+                node.nested =
+                    extract_nested_archives(window, &inner, depth - 1, options, guard, summary)?;
+            }
+            Err(e) if e.starts_with("ZIP_BOMB") => return Err(e),
+            Err(e) => node.error = Some(e),
+        }
+        tree.push(node);
+    }
+    Ok(tree)
+}
+
+// 분할 조각을 임시 폴더(mhzipy-split 아래 새 폴더)에 원래 ZIP 이름으로 합치고 그 폴더를 반환
+fn join_split_parts(target: &Path) -> Result<PathBuf, String> {
+    // 같은 분할 압축을 동시에 여러 번 풀어도 겹치지 않도록 매번 새 폴더 사용
+    let root = std::env::temp_dir().join("mhzipy-split");
+    fs::create_dir_all(&root).map_err(|e| e.to_string())?;
+    let mut counter = 1;
+    let dir = loop {
+        let dir = root.join(counter.to_string());
+        match fs::create_dir(&dir) {
+            Ok(()) => break dir,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => counter += 1,
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    let joined = dir.join(target.file_name().unwrap_or_default());
+    if let Err(e) = split::join_parts(target, &joined) {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(e.to_string());
+    }
+    // 합칠 때마다 수정 시간이 바뀌면 이어하기 기록의 아카이브 식별 정보가 달라지므로 첫 조각의 수정 시간을 유지
+    let modified = fs::metadata(split::part_path(target, 1)).and_then(|m| m.modified());
+    if let Ok(modified) = modified {
+        let _ = File::options()
+            .write(true)
+            .open(&joined)
+            .and_then(|f| f.set_modified(modified));
+    }
+    Ok(dir)
+}
+
+// 해제 명령어
+// compress_files의 split_size로 만든 분할 압축 조각(archive.zip.001 등 아무 조각)을 넘기면
+// 모든 조각을 임시 폴더에 원래 이름(archive.zip)으로 합쳐서 해제한 뒤 합친 파일을 지웁니다.
+// ZIP은 extract_zip_files에 files 없이 overwrite를 true로 넘겨 모든 항목을 풀므로, 진행률/일시 정지/이어하기와
+// 경로 검사 등 동작이 같습니다 (각 인자의 의미도 extract_zip_files 참고).
+// 해제가 중간에 실패하면 대상 폴더에 이어하기 기록이 남으며, 같은 인자에 resume을 true로 주어 이어서 풀 수 있습니다.
+// .tar, .tar.gz, .tgz는 확장자로 판별해 tar로 해제합니다. (password, encoding, name_substitute, resume은 무시)
+#[tauri::command(async)]
+fn extract_zip<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
+    zip_path: String,
+    target_dir: String,
+    password: Option<String>,
+    encoding: Option<String>,
+    max_total_uncompressed: Option<u64>,
+    max_ratio: Option<u64>,
+    symlink_policy: Option<String>,
+    into_subfolder: Option<bool>,
+    extract_nested: Option<bool>,
+    max_nested_depth: Option<u32>,
+    name_substitute: Option<char>,
+    resume: Option<bool>,
+) -> Result<ExtractSummary, CommandError> {
+    if let Some(target) = split::split_target(Path::new(&zip_path)) {
+        let dir = join_split_parts(&target)?;
+        // into_subfolder의 폴더 이름이 조각 이름이 아닌 원래 ZIP 이름을 따르도록 같은 이름으로 합침
+        let joined = dir.join(target.file_name().unwrap_or_default());
+        let result = extract_zip(
+            window,
+            state,
+            joined.to_string_lossy().into_owned(),
+            target_dir,
+            password,
+            encoding,
+            max_total_uncompressed,
+            max_ratio,
+            symlink_policy,
+            into_subfolder,
+            extract_nested,
+            max_nested_depth,
+            name_substitute,
+            resume,
+        );
+        let _ = fs::remove_dir_all(&dir);
+        return result;
+    }
+
+    if tarball::detect(Path::new(&zip_path)).is_none() {
+        return extract_zip_files(
+            window,
+            state,
+            zip_path,
+            None,
+            target_dir,
+            true,
+            password,
+            None,
+            None,
+            encoding,
+            None,
+            None,
+            None,
+            None,
+            resume,
+            max_total_uncompressed,
+            max_ratio,
+            symlink_policy,
+            into_subfolder,
+            name_substitute,
+            extract_nested,
+            max_nested_depth,
+        );
+    }
+
+    let into_subfolder = into_subfolder.unwrap_or(false);
+    let target_dir = extract_target_dir(&target_dir, &zip_path, into_subfolder, false);
+    let options = UnpackOptions {
+        password: password.as_deref(),
+        encoding: parse_name_encoding(encoding.as_deref())?,
+        symlink_policy: symlink_policy
+            .as_deref()
+            .map(SymlinkPolicy::parse)
+            .transpose()?
+            .unwrap_or(SymlinkPolicy::Skip),
+        name_substitute: parse_name_substitute(name_substitute)?,
+    };
+
+    let archive_size = fs::metadata(&zip_path).map_err(|e| e.to_string())?.len();
+    let mut guard = BombGuard::new(archive_size, max_total_uncompressed, max_ratio);
+    let mut summary = ExtractSummary::default();
+    let extracted = unpack_archive(
+        &window,
+        Path::new(&zip_path),
+        &target_dir,
+        &options,
+        &mut guard,
+        &mut summary,
+    )?;
+    if extract_nested.unwrap_or(false) {
+        let depth = max_nested_depth.unwrap_or(DEFAULT_NESTED_DEPTH);
+        summary.nested = extract_nested_archives(
+            &window,
+            &extracted,
+            depth,
+            &options,
+            &mut guard,
+            &mut summary,
+        )?;
+    }
+    Ok(summary)
+}
+
+// ZIP 파일 내용 목록 조회 명령어
+#[tauri::command]
+fn list_zip_contents(
+    zip_path: String,
+    password: Option<String>,
+    encoding: Option<String>,
+) -> Result<ZipContents, CommandError> {
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let entries = read_zip_entries(&zip_path, password.as_deref(), encoding)?;
+
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let raw_comment = archive.comment();
+    // 아카이브 주석에는 UTF-8 플래그가 없으므로 올바른 UTF-8이 아닐 때만 코드 페이지로 디코딩
+    let comment = (!raw_comment.is_empty()).then(|| match encoding {
+        Some(enc) if std::str::from_utf8(raw_comment).is_err() => {
+            enc.decode(raw_comment).0.into_owned()
+        }
+        _ => String::from_utf8_lossy(raw_comment).into_owned(),
+    });
+    Ok(ZipContents { entries, comment })
+}
+
+// ZIP의 한 폴더 바로 아래 항목만 조회하는 명령어 (항목이 많은 ZIP을 트리로 나눠 불러오기용)
+// prefix는 "docs" 또는 "docs/" 형식이며, 비우면 최상위 항목을 반환합니다.
+// 폴더 레코드 없이 "a/b/c.txt"처럼 경로로만 존재하는 폴더도 폴더 항목으로 만들어 반환합니다.
+// 반환되는 이름은 ZIP 내부 전체 경로이므로 폴더 항목의 이름을 그대로 다음 prefix로 사용할 수 있습니다.
+// 목록은 중앙 디렉터리만 읽으므로 암호화된 ZIP도 비밀번호 없이 조회할 수 있습니다.
+#[tauri::command(async)]
+fn list_zip_dir(
+    zip_path: String,
+    prefix: Option<String>,
+    encoding: Option<String>,
+) -> Result<Vec<ZipEntry>, CommandError> {
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let prefix = match prefix.as_deref().map(|p| p.trim_matches('/')) {
+        Some(p) if !p.is_empty() => format!("{}/", p),
+        _ => String::new(),
+    };
+
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    // 전체 경로 -> 항목 (같은 폴더가 여러 항목에서 나오므로 이름으로 중복 제거)
+    let mut children: BTreeMap<String, ZipEntry> = BTreeMap::new();
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        let name = decode_entry_name(&file, encoding);
+        let Some(rest) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+
+        match rest.split_once('/') {
+            // 하위 폴더 (명시적 폴더 레코드이거나 더 깊은 항목의 경로에서 추론)
+            Some((child, _)) if !child.is_empty() => {
+                let dir_name = format!("{}{}/", prefix, child);
+                children.entry(dir_name.clone()).or_insert(ZipEntry {
+                    name: dir_name,
+                    is_dir: true,
+                    size: 0,
+                    is_encrypted: false,
+                });
+            }
+            Some(_) => {}
+            None if rest.is_empty() => {}
+            None => {
+                children.insert(
+                    name.clone(),
+                    ZipEntry {
+                        name,
+                        is_dir: file.is_dir(),
+                        size: file.size(),
+                        is_encrypted: file.encrypted(),
+                    },
+                );
+            }
+        }
+    }
+
+    // 폴더를 먼저, 같은 종류끼리는 이름 순
+    let mut entries: Vec<ZipEntry> = children.into_values().collect();
+    entries.sort_by_key(|e| !e.is_dir);
+    Ok(entries)
+}
+
+// list_zip_page에서 limit을 생략했을 때 한 번에 반환하는 항목 수
+const DEFAULT_ZIP_PAGE_SIZE: usize = 1000;
+
+// ZIP 목록의 한 페이지
+#[derive(serde::Serialize)]
+struct ZipPage {
+    entries: Vec<ZipEntry>,
+    // 아카이브 전체 항목 수
+    total: usize,
+}
+
+// ZIP 내용 목록을 중앙 디렉터리 순서대로 offset부터 limit개만 조회하는 명령어 (항목이 매우 많은 ZIP용)
+// list_zip_contents와 달리 이름 목록을 미리 모으지 않고 요청한 범위의 항목만 만들며,
+// 중앙 디렉터리만 읽으므로 암호화된 항목도 비밀번호 없이 크기까지 조회할 수 있습니다.
+#[tauri::command(async)]
+fn list_zip_page(
+    zip_path: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    encoding: Option<String>,
+) -> Result<ZipPage, CommandError> {
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let total = archive.len();
+    let start = offset.unwrap_or(0).min(total);
+    let end = start
+        .saturating_add(limit.unwrap_or(DEFAULT_ZIP_PAGE_SIZE))
+        .min(total);
+    let mut entries = Vec::with_capacity(end - start);
+    for i in start..end {
+        let file = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        entries.push(ZipEntry {
+            name: decode_entry_name(&file, encoding),
+            is_dir: file.is_dir(),
+            size: file.size(),
+            is_encrypted: file.encrypted(),
+        });
+    }
+    Ok(ZipPage { entries, total })
+}
+
+// ZIP 내부 항목 검색 명령어 (압축 해제 없이 항목 이름으로 검색)
+// mode는 search_mft와 같은 "substring" | "literal" | "glob" | "regex" | "fuzzy"이며 기본값은 substring입니다.
+// case_sensitive도 search_mft와 같습니다.
+#[tauri::command]
+fn search_in_zip(
+    zip_path: String,
+    query: String,
+    password: Option<String>,
+    mode: Option<String>,
+    encoding: Option<String>,
+    case_sensitive: Option<bool>,
+) -> Result<Vec<ZipEntry>, CommandError> {
+    let mode = mode
+        .as_deref()
+        .map(SearchMode::parse)
+        .transpose()?
+        .unwrap_or(SearchMode::Substring);
+    let matcher = NameMatcher::new(&query, mode, case_sensitive.unwrap_or(false))?;
+
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let entries = read_zip_entries(&zip_path, password.as_deref(), encoding)?;
+    Ok(entries
+        .into_iter()
+        .filter(|entry| matcher.matches(&entry.name).is_some())
+        .collect())
+}
+
+// 내용 검색에서 항목 하나당 읽는 기본 최대 바이트 수
+const DEFAULT_GREP_MAX_BYTES: u64 = 8 * 1024 * 1024;
+// 바이너리 판별을 위해 널 바이트를 확인하는 앞부분 길이
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+#[derive(serde::Serialize)]
+struct GrepMatch {
+    name: String,
+    // line_numbers가 true일 때만 채워지는 1부터 시작하는 줄 번호
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lines: Option<Vec<usize>>,
+    // max_bytes_per_entry에서 읽기를 멈춘 경우 true
+    truncated: bool,
+}
+
+// ZIP 내부 항목 내용 검색 명령어 (압축 해제 없이 텍스트 항목에서 query 문자열 검색)
+// 앞부분에 널 바이트가 있는 항목은 바이너리로 보고 건너뛰며,
+// 항목마다 max_bytes_per_entry(기본 8MB)까지만 읽습니다.
+// 비밀번호가 없으면 암호화된 항목은 건너뜁니다.
+#[tauri::command(async)]
+fn grep_in_zip(
+    zip_path: String,
+    query: String,
+    password: Option<String>,
+    max_bytes_per_entry: Option<u64>,
+    line_numbers: Option<bool>,
+    encoding: Option<String>,
+) -> Result<Vec<GrepMatch>, CommandError> {
+    if query.is_empty() {
+        return Err("Query is empty".into());
+    }
+    let max_bytes = max_bytes_per_entry.unwrap_or(DEFAULT_GREP_MAX_BYTES);
+    let line_numbers = line_numbers.unwrap_or(false);
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let names = entry_names(&mut archive, encoding)?;
+
+    let mut matches = Vec::new();
+    let mut buffer = Vec::new();
+    for (i, name) in names.into_iter().enumerate() {
+        let (is_dir, is_encrypted) = {
+            let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            (raw.is_dir(), raw.encrypted())
+        };
+        if is_dir {
+            continue;
+        }
+        let entry = match password {
+            Some(ref p) if is_encrypted => archive
+                .by_index_decrypt(i, p.as_bytes())
+                .map_err(|e| e.to_string())?,
+            None if is_encrypted => continue,
+            _ => archive.by_index(i).map_err(|e| e.to_string())?,
+        };
 
-            loop {
-                let n = reader.read(&mut buffer).map_err(|e| e.to_string())?;
-                if n == 0 {
-                    break;
-                }
-                zip.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
+        buffer.clear();
+        entry
+            .take(max_bytes.saturating_add(1))
+            .read_to_end(&mut buffer)
+            .map_err(|e| e.to_string())?;
+        if buffer[..buffer.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+            continue;
+        }
+        let truncated = buffer.len() as u64 > max_bytes;
+        buffer.truncate(max_bytes as usize);
 
-                processed_size += n as u64;
-                if last_emit.elapsed().as_millis() > 100 {
-                    window
-                        .emit(
-                            "compress-progress",
-                            ProgressPayload {
-                                total: total_size,
-                                processed: processed_size,
-                                filename: name.to_string(),
-                            },
-                        )
-                        .map_err(|e| e.to_string())?;
-                    last_emit = Instant::now();
-                }
+        let text = String::from_utf8_lossy(&buffer);
+        if line_numbers {
+            let lines: Vec<usize> = text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.contains(query.as_str()))
+                .map(|(n, _)| n + 1)
+                .collect();
+            if !lines.is_empty() {
+                matches.push(GrepMatch {
+                    name,
+                    lines: Some(lines),
+                    truncated,
+                });
             }
+        } else if text.contains(query.as_str()) {
+            matches.push(GrepMatch {
+                name,
+                lines: None,
+                truncated,
+            });
         }
     }
-    zip.finish().map_err(|e| e.to_string())?;
-    Ok(())
+    Ok(matches)
 }
 
-// 해제 명령어
-#[tauri::command]
-fn extract_zip(
+// 미리보기로 메모리에 읽을 수 있는 항목의 기본 최대 크기
+const DEFAULT_PREVIEW_MAX_SIZE: u64 = 32 * 1024 * 1024;
+
+// ZIP 항목 하나를 디스크에 풀지 않고 메모리로 읽는 명령어 (텍스트/이미지 미리보기용)
+// max_size(기본 32MB)보다 큰 항목은 "ENTRY_TOO_LARGE: <크기>" 오류를 반환합니다.
+#[tauri::command(async)]
+fn read_zip_entry(
     zip_path: String,
-    target_dir: String,
+    entry_name: String,
     password: Option<String>,
-) -> Result<(), String> {
+    max_size: Option<u64>,
+    encoding: Option<String>,
+) -> Result<Vec<u8>, CommandError> {
+    let max_size = max_size.unwrap_or(DEFAULT_PREVIEW_MAX_SIZE);
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let index = entry_names(&mut archive, encoding)?
+        .iter()
+        .position(|name| *name == entry_name)
+        .ok_or_else(|| format!("Entry not found: {}", entry_name))?;
+
+    let mut entry = if let Some(ref p) = password {
+        archive
+            .by_index_decrypt(index, p.as_bytes())
+            .map_err(|e| e.to_string())?
+    } else {
+        archive.by_index(index).map_err(|e| e.to_string())?
+    };
+    if entry.is_dir() {
+        return Err(format!("Entry is a directory: {}", entry_name).into());
+    }
+    if entry.size() > max_size {
+        return Err(format!("ENTRY_TOO_LARGE: {}", entry.size()).into());
+    }
+
+    // 헤더의 크기 정보가 틀린 경우에 대비해 읽는 양도 제한
+    let mut buffer = Vec::with_capacity(entry.size() as usize);
+    (&mut entry)
+        .take(max_size + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|e| e.to_string())?;
+    if buffer.len() as u64 > max_size {
+        return Err(format!("ENTRY_TOO_LARGE: {}", buffer.len()).into());
+    }
+    Ok(buffer)
+}
+
+// ZIP의 모든 파일 항목을 디스크에 풀지 않고 메모리로 읽는 명령어 (작은 설정/데이터 아카이브를 프론트엔드에서 처리)
+// 폴더 항목은 건너뛰며, 암호화된 항목은 password로 복호화합니다.
+// 중앙 디렉터리에 기록된 크기의 합이 max_total(기본 32MB)을 넘으면 읽기 전에 "TOTAL_TOO_LARGE: <크기>" 오류를 반환하고,
+// 기록된 크기가 틀려 실제로 읽은 양이 넘는 경우에도 같은 오류로 중단합니다.
+#[tauri::command(async)]
+fn extract_to_memory(
+    zip_path: String,
+    password: Option<String>,
+    max_total: Option<u64>,
+    encoding: Option<String>,
+) -> Result<Vec<BufferEntry>, CommandError> {
+    let max_total = max_total.unwrap_or(DEFAULT_PREVIEW_MAX_SIZE);
+    let encoding = parse_name_encoding(encoding.as_deref())?;
     let file = File::open(&zip_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let names = entry_names(&mut archive, encoding)?;
 
+    let mut declared = 0u64;
     for i in 0..archive.len() {
+        let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if !raw.is_dir() {
+            declared = declared.saturating_add(raw.size());
+        }
+    }
+    if declared > max_total {
+        return Err(format!("TOTAL_TOO_LARGE: {}", declared).into());
+    }
+
+    let mut entries = Vec::new();
+    let mut remaining = max_total;
+    for (i, name) in names.into_iter().enumerate() {
         let is_encrypted = {
-            let file = archive.by_index(i).map_err(|e| e.to_string())?;
-            file.encrypted()
+            let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            if raw.is_dir() {
+                continue;
+            }
+            raw.encrypted()
         };
-
-        let mut file = if is_encrypted {
+        let mut entry = if is_encrypted {
             match password {
                 Some(ref p) => archive
                     .by_index_decrypt(i, p.as_bytes())
                     .map_err(|e| e.to_string())?,
-                None => return Err("Password required".to_string()),
+                None => return Err("Password required".into()),
             }
         } else {
             archive.by_index(i).map_err(|e| e.to_string())?
         };
 
-        let outpath = match file.enclosed_name() {
-            Some(path) => Path::new(&target_dir).join(path),
-            None => continue,
-        };
+        // 헤더의 크기 정보가 틀린 경우에 대비해 남은 한도까지만 읽음
+        let mut bytes = Vec::with_capacity(entry.size().min(remaining) as usize);
+        (&mut entry)
+            .take(remaining.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .map_err(|e| e.to_string())?;
+        if bytes.len() as u64 > remaining {
+            let read = max_total - remaining + bytes.len() as u64;
+            return Err(format!("TOTAL_TOO_LARGE: {}", read).into());
+        }
+        remaining -= bytes.len() as u64;
+        entries.push(BufferEntry { name, bytes });
+    }
+    Ok(entries)
+}
 
-        if (*file.name()).ends_with('/') {
-            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                    fs::create_dir_all(p).map_err(|e| e.to_string())?;
-                }
-            }
-            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
-            io::copy(&mut file, &mut outfile).map_err(|e| e.to_string())?;
+// read_zip_entry_range로 한 번에 읽을 수 있는 최대 길이
+const MAX_RANGE_LENGTH: u64 = 4 * 1024 * 1024;
+
+// ZIP 항목의 일부 구간
+#[derive(serde::Serialize)]
+struct EntryRange {
+    data: Vec<u8>,
+    // 항목 전체 크기 (끝부분 미리보기에서 offset 계산용)
+    size: u64,
+}
+
+// ZIP 항목에서 offset부터 length 바이트만 읽는 명령어 (큰 로그 파일의 앞/뒷부분 미리보기용)
+// 압축된 항목 안에서는 탐색(seek)할 수 없으므로 offset까지는 읽어서 버립니다.
+// 따라서 뒤쪽 구간일수록 오래 걸리며, length는 MAX_RANGE_LENGTH(4MB)로 제한됩니다.
+// offset이 항목 끝을 넘으면 빈 data를 반환합니다.
+#[tauri::command(async)]
+fn read_zip_entry_range(
+    zip_path: String,
+    entry_name: String,
+    offset: u64,
+    length: u64,
+    password: Option<String>,
+    encoding: Option<String>,
+) -> Result<EntryRange, CommandError> {
+    let length = length.min(MAX_RANGE_LENGTH);
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let index = entry_names(&mut archive, encoding)?
+        .iter()
+        .position(|name| *name == entry_name)
+        .ok_or_else(|| format!("Entry not found: {}", entry_name))?;
+    let is_encrypted = archive.by_index_raw(index).map_err(|e| e.to_string())?.encrypted();
+    let mut entry = if is_encrypted {
+        match password {
+            Some(ref p) => archive
+                .by_index_decrypt(index, p.as_bytes())
+                .map_err(|e| e.to_string())?,
+            None => return Err("Password required".into()),
         }
+    } else {
+        archive.by_index(index).map_err(|e| e.to_string())?
+    };
+    if entry.is_dir() {
+        return Err(format!("Entry is a directory: {}", entry_name).into());
     }
-    Ok(())
+
+    let size = entry.size();
+    io::copy(&mut (&mut entry).take(offset), &mut io::sink()).map_err(|e| e.to_string())?;
+    let mut data = Vec::with_capacity(length.min(size.saturating_sub(offset)) as usize);
+    (&mut entry)
+        .take(length)
+        .read_to_end(&mut data)
+        .map_err(|e| e.to_string())?;
+    Ok(EntryRange { data, size })
 }
 
-// ZIP 파일 내용 목록 조회 명령어
-#[tauri::command]
-fn list_zip_contents(zip_path: String, password: Option<String>) -> Result<Vec<ZipEntry>, String> {
+// ZIP 항목 하나를 임시 폴더에 풀고 연결된 프로그램으로 여는 명령어
+// 항목마다 새 임시 폴더(%TEMP%\mhzipy-preview\<번호>)를 만들어 원래 파일 이름으로 저장하며,
+// 만든 폴더는 AppState에 기록해 두었다가 앱 종료 시 삭제합니다.
+#[tauri::command(async)]
+fn open_zip_entry(
+    state: tauri::State<'_, AppState>,
+    zip_path: String,
+    entry_name: String,
+    password: Option<String>,
+    encoding: Option<String>,
+) -> Result<(), CommandError> {
+    let encoding = parse_name_encoding(encoding.as_deref())?;
     let file = File::open(&zip_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
 
+    let index = entry_names(&mut archive, encoding)?
+        .iter()
+        .position(|name| *name == entry_name)
+        .ok_or_else(|| format!("Entry not found: {}", entry_name))?;
+    let is_encrypted = archive.by_index_raw(index).map_err(|e| e.to_string())?.encrypted();
+    let mut entry = if is_encrypted {
+        match password {
+            Some(ref p) => archive
+                .by_index_decrypt(index, p.as_bytes())
+                .map_err(|e| e.to_string())?,
+            None => return Err("Password required".into()),
+        }
+    } else {
+        archive.by_index(index).map_err(|e| e.to_string())?
+    };
+    if entry.is_dir() {
+        return Err(format!("Entry is a directory: {}", entry_name).into());
+    }
+
+    // 같은 이름의 항목을 여러 번 열어도 겹치지 않도록 항목마다 새 폴더 사용
+    let root = std::env::temp_dir().join("mhzipy-preview");
+    fs::create_dir_all(&root).map_err(|e| e.to_string())?;
+    let mut counter = 1;
+    let dir = loop {
+        let dir = root.join(counter.to_string());
+        match fs::create_dir(&dir) {
+            Ok(()) => break dir,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => counter += 1,
+            Err(e) => return Err(e.into()),
+        }
+    };
+    state.preview_dirs.lock().unwrap().push(dir.clone());
+
+    let outpath = safe_output_path(&dir, flattened_name(&entry_name))?;
+    let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
+    io::copy(&mut entry, &mut outfile).map_err(|e| e.to_string())?;
+    restore_modified_time(&outfile, entry.last_modified());
+    drop(outfile);
+
+    Ok(open::that(&outpath)?)
+}
+
+// ZIP 항목 목록 읽기 (암호 문제로 열 수 없는 항목은 이름만 채움)
+fn read_zip_entries(
+    zip_path: &str,
+    password: Option<&str>,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> Result<Vec<ZipEntry>, String> {
+    let file = File::open(zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
     // 파일 이름 목록을 미리 수집 (암호 문제로 by_index 실패 시 사용)
-    let names: Vec<String> = archive.file_names().map(|s| s.to_string()).collect();
+    let names = entry_names(&mut archive, encoding)?;
 
     let mut entries = Vec::new();
     for i in 0..archive.len() {
-        let file_result = if let Some(ref p) = password {
+        let file_result = if let Some(p) = password {
             archive.by_index_decrypt(i, p.as_bytes())
         } else {
             archive.by_index(i)
@@ -326,7 +3783,7 @@ fn list_zip_contents(zip_path: String, password: Option<String>) -> Result<Vec<Z
         match file_result {
             Ok(file) => {
                 entries.push(ZipEntry {
-                    name: file.name().to_string(),
+                    name: decode_entry_name(&file, encoding),
                     is_dir: file.is_dir(),
                     size: file.size(),
                     is_encrypted: file.encrypted(),
@@ -355,182 +3812,873 @@ fn list_zip_contents(zip_path: String, password: Option<String>) -> Result<Vec<Z
     Ok(entries)
 }
 
+// 이미 존재하는 파일을 해제할 때의 처리 방식
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OverwritePolicy {
+    Skip,
+    Overwrite,
+    OverwriteIfNewer,
+    Rename,
+}
+
+impl OverwritePolicy {
+    fn parse(policy: &str) -> Result<Self, String> {
+        match policy {
+            "skip" => Ok(OverwritePolicy::Skip),
+            "overwrite" => Ok(OverwritePolicy::Overwrite),
+            "overwrite_if_newer" => Ok(OverwritePolicy::OverwriteIfNewer),
+            "rename" => Ok(OverwritePolicy::Rename),
+            other => Err(format!("Unknown overwrite policy: {}", other)),
+        }
+    }
+}
+
+// "name.ext"가 이미 있으면 "name (1).ext", "name (2).ext", ... 중 비어 있는 경로를 찾습니다.
+fn unique_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{} ({}){}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap()
+}
+
+#[derive(Clone, serde::Serialize)]
+struct EntryError {
+    name: String,
+    error: String,
+}
+
+#[derive(serde::Serialize)]
+struct RenamedEntry {
+    name: String,
+    path: String,
+}
+
+// 심볼릭 링크 항목(유닉스 모드가 S_IFLNK)의 처리 방식
+// create는 실제 링크를 만들며 Windows에서는 관리자 권한이나 개발자 모드가 필요합니다.
+// 링크 대상이 대상 폴더를 벗어나면 만들지 않고 SYMLINK_ESCAPES 오류를 반환합니다.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SymlinkPolicy {
+    Skip,
+    Create,
+    // 링크 대상 경로를 내용으로 하는 일반 파일로 저장
+    Text,
+}
+
+impl SymlinkPolicy {
+    fn parse(policy: &str) -> Result<Self, String> {
+        match policy {
+            "skip" => Ok(SymlinkPolicy::Skip),
+            "create" => Ok(SymlinkPolicy::Create),
+            "text" => Ok(SymlinkPolicy::Text),
+            other => Err(format!("Unknown symlink policy: {}", other)),
+        }
+    }
+}
+
+// 링크 대상 경로로 읽을 최대 길이
+const SYMLINK_TARGET_MAX: u64 = 4096;
+
+#[derive(serde::Serialize)]
+struct SymlinkEntry {
+    name: String,
+    target: String,
+    // "skipped" | "created" | "text"
+    action: &'static str,
+}
+
+fn is_symlink_mode(mode: Option<u32>) -> bool {
+    mode.is_some_and(|mode| mode & 0o170000 == 0o120000)
+}
+
+// 링크 위치(대상 폴더 내부)에서 링크 대상을 따라가면 대상 폴더를 벗어나는지 확인
+fn symlink_escapes(root: &Path, link: &Path, target: &str) -> bool {
+    let Ok(relative) = link.parent().unwrap_or(root).strip_prefix(root) else {
+        return true;
+    };
+    let mut depth = relative.components().count();
+    for component in Path::new(&target.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return true,
+        }
+    }
+    false
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &str, link: &Path) -> io::Result<()> {
+    let target = PathBuf::from(target.replace('/', "\\"));
+    // 대상이 폴더면 폴더 링크, 아니면(아직 없는 대상 포함) 파일 링크
+    let resolved = link
+        .parent()
+        .map_or_else(|| target.clone(), |p| p.join(&target));
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(&target, link)
+    } else {
+        std::os::windows::fs::symlink_file(&target, link)
+    }
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &str, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+// 심볼릭 링크 항목을 정책에 따라 처리 (항목 내용이 링크 대상 경로)
+fn extract_symlink(
+    entry: &mut impl Read,
+    name: &str,
+    outpath: &Path,
+    root: &Path,
+    policy: SymlinkPolicy,
+) -> Result<SymlinkEntry, String> {
+    let mut raw = Vec::new();
+    entry
+        .take(SYMLINK_TARGET_MAX)
+        .read_to_end(&mut raw)
+        .map_err(|e| e.to_string())?;
+    let target = String::from_utf8_lossy(&raw).into_owned();
+
+    if policy != SymlinkPolicy::Skip {
+        if let Some(p) = outpath.parent() {
+            fs::create_dir_all(p).map_err(|e| e.to_string())?;
+        }
+    }
+    let action = match policy {
+        SymlinkPolicy::Skip => "skipped",
+        SymlinkPolicy::Text => {
+            fs::write(outpath, &target).map_err(|e| e.to_string())?;
+            "text"
+        }
+        SymlinkPolicy::Create => {
+            if symlink_escapes(root, outpath, &target) {
+                return Err(format!("SYMLINK_ESCAPES: {}", name));
+            }
+            if outpath.symlink_metadata().is_ok() {
+                fs::remove_file(outpath).map_err(|e| e.to_string())?;
+            }
+            create_symlink(&target, outpath).map_err(|e| e.to_string())?;
+            "created"
+        }
+    };
+    Ok(SymlinkEntry {
+        name: name.to_string(),
+        target,
+        action,
+    })
+}
+
+// 압축 해제 결과 요약
+#[derive(Default, serde::Serialize)]
+struct ExtractSummary {
+    // 이름 변경 정책으로 다른 이름으로 저장된 항목
+    renamed: Vec<RenamedEntry>,
+    // 이미 존재하여 건너뛴 항목
+    skipped: Vec<String>,
+    // continue_on_error일 때 실패한 항목
+    failed: Vec<EntryError>,
+    // 심볼릭 링크 항목과 처리 결과 (symlink_policy 참고)
+    symlinks: Vec<SymlinkEntry>,
+    // Windows에서 쓸 수 없는 문자를 바꿔 저장한 항목 (sanitize_entry_name 참고)
+    sanitized: Vec<RenamedEntry>,
+    // extract_nested로 해제한 중첩 아카이브 트리
+    nested: Vec<NestedArchive>,
+}
+
+// 폴더 구조를 무시할 때의 출력 이름 (마지막 경로 요소만 사용)
+fn flattened_name(name: &str) -> &str {
+    name.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(name)
+}
+
+// 항목 이름에서 앞쪽 경로(prefix)를 제거 (경로 요소 단위로 일치해야 함)
+fn strip_entry_prefix<'a>(name: &'a str, prefix: &str) -> Option<&'a str> {
+    let prefix = prefix.trim_matches(['/', '\\']);
+    if prefix.is_empty() {
+        return Some(name);
+    }
+    let rest = name.strip_prefix(prefix)?;
+    if rest.is_empty() || rest.starts_with(['/', '\\']) {
+        Some(rest.trim_start_matches(['/', '\\']))
+    } else {
+        None
+    }
+}
+
+// 해제할 항목 (중앙 디렉터리에서 한 번에 모은 정보)
+// outpath는 경로 검사 결과이며, 실패한 항목은 continue_on_error일 때 해당 항목만 실패로 기록됩니다.
+struct PlannedExtract {
+    index: usize,
+    name: String,
+    is_dir: bool,
+    encrypted: bool,
+    size: u64,
+    outpath: Result<PathBuf, String>,
+}
+
+// 압축을 풀었을 때의 크기
+#[derive(serde::Serialize)]
+struct ZipSize {
+    total: u64,
+    // 최상위 폴더별 크기 (최상위에 바로 있는 파일은 "" 키에 합산), breakdown일 때만 채움
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folders: Option<BTreeMap<String, u64>>,
+}
+
+// 항목의 압축 해제 크기를 합산 (중앙 디렉터리의 크기 정보만 사용하므로 비밀번호가 필요 없음)
+fn zip_uncompressed_size(
+    zip_path: &str,
+    encoding: Option<&'static encoding_rs::Encoding>,
+    breakdown: bool,
+) -> Result<ZipSize, String> {
+    let file = File::open(zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let names = entry_names(&mut archive, encoding)?;
+    let mut total = 0u64;
+    let mut folders = breakdown.then(BTreeMap::new);
+    for (i, name) in names.iter().enumerate() {
+        let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if raw.is_dir() {
+            continue;
+        }
+        total += raw.size();
+        if let Some(ref mut folders) = folders {
+            let folder = match name.split_once('/') {
+                Some((top, _)) => top.to_string(),
+                None => String::new(),
+            };
+            *folders.entry(folder).or_insert(0) += raw.size();
+        }
+    }
+    Ok(ZipSize { total, folders })
+}
+
+// 경로가 속한 드라이브의 남은 공간 (마운트 지점이 가장 길게 일치하는 디스크 기준)
+fn available_space(path: &Path) -> Option<u64> {
+    // 아직 없는 대상 폴더라면 존재하는 가장 가까운 상위 폴더로 판단
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let path = existing.canonicalize().ok()?;
+    let path = path
+        .to_string_lossy()
+        .trim_start_matches(r"\\?\")
+        .to_lowercase();
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|d| path.starts_with(&d.mount_point().to_string_lossy().to_lowercase()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| d.available_space())
+}
+
+// ZIP의 압축 해제 크기 조회 명령어
+// breakdown이면 최상위 폴더별 크기도 함께 반환합니다 (트리맵 표시용).
+#[tauri::command(async)]
+fn get_zip_uncompressed_size(
+    zip_path: String,
+    encoding: Option<String>,
+    breakdown: Option<bool>,
+) -> Result<ZipSize, CommandError> {
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let breakdown = breakdown.unwrap_or(false);
+    Ok(zip_uncompressed_size(&zip_path, encoding, breakdown)?)
+}
+
+#[derive(serde::Serialize)]
+struct SpaceCheck {
+    required: u64,
+    // 드라이브를 찾지 못하면 None
+    available: Option<u64>,
+    enough: bool,
+}
+
+// 압축을 풀 공간이 대상 드라이브에 충분한지 확인하는 명령어
+// 남은 공간을 알 수 없으면 enough를 true로 두어 해제를 막지 않습니다.
+#[tauri::command(async)]
+fn check_extract_space(zip_path: String, target_dir: String) -> Result<SpaceCheck, CommandError> {
+    let required = zip_uncompressed_size(&zip_path, None, false)?.total;
+    let available = available_space(Path::new(&target_dir));
+    Ok(SpaceCheck {
+        required,
+        available,
+        enough: available.map_or(true, |available| available >= required),
+    })
+}
+
 // 선택된 ZIP 파일 내용 압축 해제 명령어
-#[tauri::command]
-fn extract_zip_files(
-    window: Window,
+// files에는 항목 이름, 폴더 접두사 또는 glob 패턴을 지정할 수 있습니다 (EntrySelector 참고).
+// overwrite_policy를 지정하면 충돌하는 파일마다 정책을 적용하고,
+// 지정하지 않으면 기존처럼 overwrite가 false일 때 충돌 시 FILE_EXISTS 오류를 반환합니다.
+// flatten이면 내부 폴더 구조를 무시하고 모든 파일을 target_dir에 바로 풀며, 폴더 항목은 건너뜁니다.
+// 이때 서로 다른 폴더의 같은 이름 파일끼리 겹치면 정책과 관계없이 뒤의 파일 이름에 번호를 붙입니다.
+// strip_prefix를 지정하면 각 항목의 앞쪽 경로를 제거하고 풉니다 (예: "project/src"를 대상 폴더 바로 아래로).
+// 선택된 항목 중 prefix로 시작하지 않는 항목이 있으면 PREFIX_MISMATCH 오류를 반환합니다.
+// skip_size_scan이면 전체 크기를 합산하지 않고 바로 해제를 시작하며,
+// 진행 이벤트에는 처리한 바이트만 보고합니다 (total은 0, etaSecs는 없음).
+// max_total_uncompressed, max_ratio로 압축 폭탄을 막으며 (bomb 모듈 참고, max_ratio 기본값 1000, 0이면 검사 안 함),
+// 압축 폭탄이면 continue_on_error와 관계없이 중단합니다.
+// symlink_policy로 심볼릭 링크 항목의 처리 방식을 정하며 (SymlinkPolicy 참고, 기본값 skip),
+// 처리한 링크는 결과 요약의 symlinks에 담깁니다.
+// 해제하는 동안 완료한 항목을 대상 폴더의 기록 파일(resume 모듈 참고)에 남기고, 파일은 임시 이름으로
+// 다 쓴 뒤 원래 이름으로 바꿉니다. 중간에 앱이 종료되었으면 같은 인자에 resume을 true로 주어 다시 호출하면
+// 기록된 항목 중 파일이 그대로 남아 있는 것은 건너뛰고 나머지만 풉니다.
+// 기록이 없으면 RESUME_NOT_FOUND, 아카이브가 바뀌었으면 RESUME_MISMATCH 오류를 반환하며,
+// 모든 항목을 실패 없이 풀면 기록 파일을 삭제합니다.
+// into_subfolder면 target_dir 아래에 아카이브 이름의 폴더를 만들어 그 안에 풀며 (extract_target_dir 참고),
+// 이미 있는 폴더는 rename 정책일 때만 새 이름을 씁니다 (이어하기 제외).
+// ZIP 항목 이름에 Windows에서 쓸 수 없는 문자(<>:"|?* 등)가 있으면 name_substitute(기본 '_')로 바꾸고
+// 끝의 점/공백을 제거해 풀며, 바뀐 항목은 결과 요약의 sanitized에 담깁니다 (sanitize_entry_name 참고).
+// extract_nested면 풀린 파일 중 .zip/.tar 계열 아카이브를 max_nested_depth(기본 3)단계까지
+// 제자리에 다시 풀고 결과 요약의 nested에 트리로 담습니다 (extract_nested_archives 참고).
+// 해제 중에도 pause_operation/resume_operation 명령을 처리할 수 있도록 별도 스레드에서 실행합니다.
+#[tauri::command(async)]
+fn extract_zip_files<R: Runtime>(
+    window: Window<R>,
+    state: tauri::State<'_, AppState>,
     zip_path: String,
     files: Option<Vec<String>>,
     target_dir: String,
     overwrite: bool,
     password: Option<String>,
-) -> Result<(), String> {
+    overwrite_policy: Option<String>,
+    continue_on_error: Option<bool>,
+    encoding: Option<String>,
+    flatten: Option<bool>,
+    strip_prefix: Option<String>,
+    buffer_size: Option<usize>,
+    skip_size_scan: Option<bool>,
+    resume: Option<bool>,
+    max_total_uncompressed: Option<u64>,
+    max_ratio: Option<u64>,
+    symlink_policy: Option<String>,
+    into_subfolder: Option<bool>,
+    name_substitute: Option<char>,
+    extract_nested: Option<bool>,
+    max_nested_depth: Option<u32>,
+) -> Result<ExtractSummary, CommandError> {
+    let buffer_size = resolve_buffer_size(buffer_size)?;
+    let extract_nested = extract_nested.unwrap_or(false);
+    let name_substitute = parse_name_substitute(name_substitute)?;
+    let flatten = flatten.unwrap_or(false);
+    let skip_size_scan = skip_size_scan.unwrap_or(false);
+    let encoding = parse_name_encoding(encoding.as_deref())?;
+    let policy = overwrite_policy
+        .as_deref()
+        .map(OverwritePolicy::parse)
+        .transpose()?;
+    let symlink_policy = symlink_policy
+        .as_deref()
+        .map(SymlinkPolicy::parse)
+        .transpose()?
+        .unwrap_or(SymlinkPolicy::Skip);
+
     let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let archive_size = file.metadata().map_err(|e| e.to_string())?.len();
     let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
-    let target_path = Path::new(&target_dir);
-
-    // 추출할 파일 인덱스 식별 및 전체 크기 계산
-    // 먼저 파일 이름 목록을 확보한 뒤, 제공된 비밀번호로 항목을 열어보거나
-    // 암호가 필요하면 크기를 알 수 없으므로 0으로 처리하여 진행합니다.
-    let names: Vec<String> = archive.file_names().map(|s| s.to_string()).collect();
-    let mut indices = Vec::new();
-    let mut total_size = 0u64;
-    for i in 0..archive.len() {
-        let name = names
-            .get(i)
-            .cloned()
-            .unwrap_or_else(|| format!("Unknown_{}", i));
+    let mut guard = BombGuard::new(archive_size, max_total_uncompressed, max_ratio);
+    // 이어하기라면 처음 해제할 때 만든 폴더를 그대로 사용
+    let rename_subfolder = policy == Some(OverwritePolicy::Rename) && !resume.unwrap_or(false);
+    let target_dir = extract_target_dir(
+        &target_dir,
+        &zip_path,
+        into_subfolder.unwrap_or(false),
+        rename_subfolder,
+    );
+    let target_path = target_dir.as_path();
+    fs::create_dir_all(target_path).map_err(|e| e.to_string())?;
 
+    // 이어하기 기록 (기록된 항목 중 파일이 바뀌거나 지워진 것은 다시 풂)
+    let fingerprint =
+        ArchiveFingerprint::of(Path::new(&zip_path), archive.len()).map_err(|e| e.to_string())?;
+    let resume_path = resume::path_for(target_path, Path::new(&zip_path));
+    let mut completed = if resume.unwrap_or(false) {
+        let mut completed = resume::load(&resume_path, fingerprint)?;
+        completed.retain(|_, entry| entry.is_intact());
+        completed
+    } else {
+        std::collections::HashMap::new()
+    };
+    // 중앙 디렉터리를 한 번만 읽어 해제할 항목의 정보를 모아둠
+    // by_index_raw는 복호화하지 않으므로 암호화된 항목도 비밀번호 없이 이름과 크기를 알 수 있습니다.
+    let selectors = files
+        .as_ref()
+        .map(|list| {
+            list.iter()
+                .map(|f| EntrySelector::new(f))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    let names = entry_names(&mut archive, encoding)?;
+    let prefix = strip_prefix.as_deref().unwrap_or("");
+    let mut planned = Vec::new();
+    let mut declared = Vec::new();
+    let mut sanitized = Vec::new();
+    for (i, name) in names.into_iter().enumerate() {
         // Determine whether this entry is targeted
-        let is_target = if let Some(ref target_files) = files {
-            target_files.iter().any(|f| {
-                if *f == name {
-                    return true;
-                }
-                if f.ends_with('/') && name.starts_with(f) {
-                    return true;
-                }
-                if name.starts_with(f) && name.chars().nth(f.len()) == Some('/') {
-                    return true;
-                }
-                false
-            })
+        let is_target = if let Some(ref selectors) = selectors {
+            selectors.iter().any(|s| s.matches(&name))
         } else {
             true
         };
-
         if !is_target {
             continue;
         }
 
-        // Try to open the entry using provided password if any, otherwise try without.
-        let file_result = if let Some(ref p) = password {
-            archive.by_index_decrypt(i, p.as_bytes())
-        } else {
-            archive.by_index(i)
+        // 출력 상대 경로 (prefix 제거 후 flatten 적용)
+        let Some(stripped) = strip_entry_prefix(&name, prefix) else {
+            return Err(format!("PREFIX_MISMATCH: {}", name).into());
         };
+        let output_name = if flatten { flattened_name(stripped) } else { stripped };
+        let output_name = sanitize_entry_name(output_name, name_substitute);
+        let outpath = safe_output_path(target_path, &output_name);
+        if let (std::borrow::Cow::Owned(_), Ok(path)) = (&output_name, &outpath) {
+            sanitized.push(RenamedEntry {
+                name: name.clone(),
+                path: path.to_string_lossy().into_owned(),
+            });
+        }
 
-        match file_result {
-            Ok(f) => {
-                indices.push(i);
-                if !f.is_dir() {
-                    total_size += f.size();
-                }
+        let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if !raw.is_dir() {
+            declared.push((raw.size(), raw.compressed_size()));
+        }
+        planned.push(PlannedExtract {
+            index: i,
+            is_dir: raw.is_dir(),
+            encrypted: raw.encrypted(),
+            size: raw.size(),
+            outpath,
+            name,
+        });
+    }
+    guard.check_declared(declared)?;
+
+    // 덮어쓰기 방지 체크 (정책 없이 overwrite가 false일 경우, 폴더와 이미 완료한 항목은 체크 제외)
+    if policy.is_none() && !overwrite {
+        for entry in planned
+            .iter()
+            .filter(|e| !e.is_dir && !completed.contains_key(&e.index))
+        {
+            // 경로 검사에 걸린 항목은 여기서 중단하지 않고 아래에서 처리 (continue_on_error면 실패 목록에 기록)
+            if entry.outpath.as_ref().is_ok_and(|outpath| outpath.exists()) {
+                return Err("FILE_EXISTS".into());
             }
-            Err(e) => {
-                let err_str = e.to_string();
-                // If password is required or invalid, include the index but size unknown (0)
-                if err_str.contains("Password required") || err_str.contains("Invalid password") {
-                    indices.push(i);
-                    // size unknown when encrypted and password not provided or invalid
-                } else {
-                    return Err(err_str);
+        }
+    }
+
+    // 사전 검사를 모두 통과한 뒤에 기록을 시작 (거부된 해제는 대상 폴더에 기록 파일을 남기지 않음)
+    let mut resume_log = if resume.unwrap_or(false) {
+        ResumeLog::append(&resume_path)
+    } else {
+        ResumeLog::create(&resume_path, fingerprint)
+    }
+    .map_err(|e| e.to_string())?;
+
+    let policy = policy.unwrap_or(OverwritePolicy::Overwrite);
+    let mut summary = ExtractSummary {
+        sanitized,
+        ..Default::default()
+    };
+    let pause = state.pause.begin(None);
+    let mut progress = if skip_size_scan {
+        ProgressEmitter::new(&window, "extract-progress", 0).indeterminate()
+    } else {
+        let total_size: u64 = planned.iter().filter(|e| !e.is_dir).map(|e| e.size).sum();
+        ProgressEmitter::new(&window, "extract-progress", total_size)
+    }
+    .with_pause(&pause);
+    let mut buffer = vec![0u8; buffer_size];
+    // flatten일 때 이번 해제에서 이미 쓴 경로 (항목끼리의 이름 충돌 감지용)
+    // 이어하기라면 이전에 쓴 파일도 포함
+    let mut written: std::collections::HashSet<PathBuf> = completed
+        .values()
+        .filter_map(|entry| entry.path().map(Path::to_path_buf))
+        .collect();
+    // extract_nested일 때 중첩 아카이브를 찾을 해제한 파일 (이어하기로 건너뛴 파일 포함)
+    let mut extracted = Vec::new();
+
+    // 파일 추출 실행
+    for entry in &planned {
+        if let Some(done) = completed.remove(&entry.index) {
+            if let (true, Some(path)) = (extract_nested, done.path()) {
+                extracted.push(path.to_path_buf());
+            }
+            progress.advance(if entry.is_dir { 0 } else { entry.size }, &entry.name)?;
+            continue;
+        }
+        let result = (|| -> Result<(), String> {
+            let mut outpath = entry.outpath.clone()?;
+            let file_name = entry.name.clone();
+
+            if entry.is_dir {
+                if !flatten {
+                    fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
+                }
+                resume_log
+                    .record(entry.index, None, 0)
+                    .map_err(|e| e.to_string())?;
+            } else {
+                // 암호화된 항목만 복호화하고, 평문 항목은 비밀번호가 있어도 그대로 읽음
+                let mut file = match password {
+                    Some(ref p) if entry.encrypted => archive
+                        .by_index_decrypt(entry.index, p.as_bytes())
+                        .map_err(|e| e.to_string())?,
+                    _ => archive.by_index(entry.index).map_err(|e| e.to_string())?,
+                };
+
+                // 충돌 시 정책 적용
+                if flatten && written.contains(&outpath) {
+                    outpath = unique_path(&outpath);
+                    summary.renamed.push(RenamedEntry {
+                        name: file_name.clone(),
+                        path: outpath.to_string_lossy().into_owned(),
+                    });
+                } else if outpath.exists() {
+                    match policy {
+                        OverwritePolicy::Overwrite => {}
+                        OverwritePolicy::Skip => {
+                            progress.advance(entry.size, &file_name)?;
+                            summary.skipped.push(file_name);
+                            resume_log
+                                .record(entry.index, None, 0)
+                                .map_err(|e| e.to_string())?;
+                            return Ok(());
+                        }
+                        OverwritePolicy::OverwriteIfNewer => {
+                            let existing = fs::metadata(&outpath).and_then(|m| m.modified()).ok();
+                            let incoming = file.last_modified().and_then(from_zip_datetime);
+                            if let (Some(existing), Some(incoming)) = (existing, incoming) {
+                                if incoming <= existing {
+                                    progress.advance(entry.size, &file_name)?;
+                                    summary.skipped.push(file_name);
+                                    resume_log
+                                        .record(entry.index, None, 0)
+                                        .map_err(|e| e.to_string())?;
+                                    return Ok(());
+                                }
+                            }
+                        }
+                        OverwritePolicy::Rename => {
+                            outpath = unique_path(&outpath);
+                            summary.renamed.push(RenamedEntry {
+                                name: file_name.clone(),
+                                path: outpath.to_string_lossy().into_owned(),
+                            });
+                        }
+                    }
+                }
+
+                if is_symlink_mode(file.unix_mode()) {
+                    let link = extract_symlink(
+                        &mut file,
+                        &file_name,
+                        &outpath,
+                        target_path,
+                        symlink_policy,
+                    )?;
+                    progress.advance(entry.size, &file_name)?;
+                    summary.symlinks.push(link);
+                    resume_log
+                        .record(entry.index, None, 0)
+                        .map_err(|e| e.to_string())?;
+                    return Ok(());
+                }
+
+                if let Some(p) = outpath.parent() {
+                    if !p.exists() {
+                        fs::create_dir_all(p).map_err(|e| e.to_string())?;
+                    }
+                }
+                let partial = resume::partial_path(&outpath);
+                let mut outfile = File::create(&partial).map_err(|e| e.to_string())?;
+
+                let mut size = 0u64;
+                let copied = (|| -> Result<(), String> {
+                    loop {
+                        let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
+                        if n == 0 {
+                            return Ok(());
+                        }
+                        guard.add(n as u64)?;
+                        outfile.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
+                        size += n as u64;
+                        progress.advance(n as u64, &file_name)?;
+                    }
+                })();
+                restore_modified_time(&outfile, file.last_modified());
+                drop(outfile);
+                // 실패하면 덜 쓴 임시 파일은 지움
+                if let Err(error) = copied {
+                    let _ = fs::remove_file(&partial);
+                    return Err(error);
+                }
+                fs::rename(&partial, &outpath).map_err(|e| e.to_string())?;
+                resume_log
+                    .record(entry.index, Some(&outpath), size)
+                    .map_err(|e| e.to_string())?;
+                if extract_nested {
+                    extracted.push(outpath.clone());
+                }
+                if flatten {
+                    written.insert(outpath);
                 }
             }
+            Ok(())
+        })();
+
+        // continue_on_error이면 실패한 항목을 기록하고 계속 진행
+        if let Err(error) = result {
+            if !continue_on_error.unwrap_or(false) || error.starts_with("ZIP_BOMB") {
+                return Err(error.into());
+            }
+            let failure = EntryError {
+                name: entry.name.clone(),
+                error,
+            };
+            let _ = window.emit("extract-warning", &failure);
+            summary.failed.push(failure);
         }
     }
+    if extract_nested {
+        let options = UnpackOptions {
+            password: password.as_deref(),
+            encoding,
+            symlink_policy,
+            name_substitute,
+        };
+        let depth = max_nested_depth.unwrap_or(DEFAULT_NESTED_DEPTH);
+        summary.nested = extract_nested_archives(
+            &window,
+            &extracted,
+            depth,
+            &options,
+            &mut guard,
+            &mut summary,
+        )?;
+    }
+    // 실패한 항목이 있으면 이어하기로 다시 시도할 수 있도록 기록을 남겨둠
+    drop(resume_log);
+    if summary.failed.is_empty() {
+        let _ = fs::remove_file(&resume_path);
+    }
+    // 완료 이벤트 전송
+    progress.complete(None)?;
+    Ok(summary)
+}
 
-    // 덮어쓰기 방지 체크 (overwrite가 false일 경우)
-    if !overwrite {
-        for &i in &indices {
-            let file = if let Some(ref p) = password {
-                archive
-                    .by_index_decrypt(i, p.as_bytes())
-                    .map_err(|e| e.to_string())?
-            } else {
-                archive.by_index(i).map_err(|e| e.to_string())?
-            };
-            if file.is_dir() {
-                continue;
-            } // 폴더는 체크 제외
+#[derive(serde::Serialize)]
+struct EntryCheck {
+    name: String,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
 
-            let outpath = match file.enclosed_name() {
-                Some(path) => target_path.join(path),
-                None => continue,
-            };
-            if outpath.exists() {
-                return Err("FILE_EXISTS".to_string());
-            }
-        }
+#[derive(serde::Serialize)]
+struct VerifyResult {
+    ok: bool,
+    entries: Vec<EntryCheck>,
+    // 실패한 항목 이름 목록
+    failed: Vec<String>,
+}
+
+// 압축 해제 없이 모든 항목을 끝까지 읽어 CRC32를 검증하는 명령어
+#[tauri::command(async)]
+fn verify_zip<R: Runtime>(
+    window: Window<R>,
+    zip_path: String,
+    password: Option<String>,
+) -> Result<VerifyResult, CommandError> {
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut total_size = 0u64;
+    for i in 0..archive.len() {
+        total_size += archive.by_index_raw(i).map_err(|e| e.to_string())?.size();
     }
 
-    let mut processed_size = 0u64;
-    let mut last_emit = Instant::now();
+    let mut entries = Vec::new();
+    let mut progress = ProgressEmitter::new(&window, "verify-progress", total_size);
     let mut buffer = [0u8; 65536]; // 64KB 버퍼
 
-    // 파일 추출 실행
-    for &i in &indices {
-        let mut file = if let Some(ref p) = password {
-            archive
-                .by_index_decrypt(i, p.as_bytes())
-                .map_err(|e| e.to_string())?
-        } else {
-            archive.by_index(i).map_err(|e| e.to_string())?
-        };
-        let outpath = match file.enclosed_name() {
-            Some(path) => target_path.join(path),
-            None => continue,
+    for i in 0..archive.len() {
+        let (name, is_encrypted) = {
+            let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+            (raw.name().to_string(), raw.encrypted())
         };
 
-        let file_name = file.name().to_string();
-
-        if file.is_dir() {
-            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
-        } else {
-            if let Some(p) = outpath.parent() {
-                if !p.exists() {
-                    fs::create_dir_all(p).map_err(|e| e.to_string())?;
+        let check = (|| -> Result<(), String> {
+            let mut file = if is_encrypted {
+                match password {
+                    Some(ref p) => archive
+                        .by_index_decrypt(i, p.as_bytes())
+                        .map_err(|e| e.to_string())?,
+                    None => return Err("Password required".to_string()),
                 }
-            }
-            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
+            } else {
+                archive.by_index(i).map_err(|e| e.to_string())?
+            };
 
+            // 끝까지 읽으면 zip 크레이트가 CRC32 불일치를 오류로 반환합니다.
             loop {
                 let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
                 if n == 0 {
                     break;
                 }
-                outfile.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
-
-                processed_size += n as u64;
-                if last_emit.elapsed().as_millis() > 100 {
-                    window
-                        .emit(
-                            "extract-progress",
-                            ProgressPayload {
-                                total: total_size,
-                                processed: processed_size,
-                                filename: file_name.clone(),
-                            },
-                        )
-                        .map_err(|e| e.to_string())?;
-                    last_emit = Instant::now();
-                }
+                progress.advance(n as u64, &name)?;
             }
-        }
+            Ok(())
+        })();
+
+        entries.push(EntryCheck {
+            name,
+            ok: check.is_ok(),
+            error: check.err(),
+        });
     }
+
     // 완료 이벤트 전송
-    window
-        .emit(
-            "extract-progress",
-            ProgressPayload {
-                total: total_size,
-                processed: total_size,
-                filename: "Complete".to_string(),
-            },
-        )
-        .map_err(|e| e.to_string())?;
-    Ok(())
+    progress.complete(None)?;
+
+    let failed: Vec<String> = entries
+        .iter()
+        .filter(|e| !e.ok)
+        .map(|e| e.name.clone())
+        .collect();
+    Ok(VerifyResult {
+        ok: failed.is_empty(),
+        entries,
+        failed,
+    })
+}
+
+// 압축 해제 전에 비밀번호가 맞는지 확인하는 명령어
+// 암호화된 항목 중 가장 작은 파일 하나를 끝까지 읽어 체크 바이트와 CRC32(AES는 인증 코드)까지 검사합니다.
+// 비밀번호가 틀리면 false, 암호화된 항목이 없으면 "NOT_ENCRYPTED", 그 외 손상은 오류를 반환합니다.
+#[tauri::command(async)]
+fn verify_zip_password(zip_path: String, password: String) -> Result<bool, CommandError> {
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut target: Option<(usize, u64)> = None;
+    for i in 0..archive.len() {
+        let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        let smaller = target.map_or(true, |(_, size)| raw.compressed_size() < size);
+        if raw.encrypted() && !raw.is_dir() && smaller {
+            target = Some((i, raw.compressed_size()));
+        }
+    }
+    let Some((index, _)) = target else {
+        return Err("NOT_ENCRYPTED".into());
+    };
+
+    let mut entry = match archive.by_index_decrypt(index, password.as_bytes()) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::InvalidPassword) => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+
+    // ZipCrypto의 체크 바이트는 1/256 확률로 우연히 맞을 수 있으므로 끝까지 읽어 확인
+    match io::copy(&mut entry, &mut io::sink()) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// ZIP의 암호화 정보
+#[derive(serde::Serialize)]
+struct EncryptionInfo {
+    encrypted: bool,
+    // 사용된 암호화 방식 ("zipcrypto", "aes128", "aes192", "aes256")
+    methods: Vec<&'static str>,
+    // 암호화된 파일과 암호화되지 않은 파일이 섞여 있는지
+    mixed: bool,
+    #[serde(rename = "encryptedEntries")]
+    encrypted_entries: usize,
+    #[serde(rename = "totalEntries")]
+    total_entries: usize,
+}
+
+// 중앙 디렉터리 extra field에서 AES 헤더(0x9901)를 찾아 암호화 강도를 반환 (AES가 아니면 None)
+// 헤더 데이터: 벤더 버전(2) + 벤더 ID(2) + 강도(1) + 실제 압축 방식(2)
+fn aes_strength(extra: &[u8]) -> Option<&'static str> {
+    let mut rest = extra;
+    while rest.len() >= 4 {
+        let id = u16::from_le_bytes([rest[0], rest[1]]);
+        let len = u16::from_le_bytes([rest[2], rest[3]]) as usize;
+        let data = rest.get(4..4 + len)?;
+        if id == 0x9901 && len >= 5 {
+            return match data[4] {
+                0x01 => Some("aes128"),
+                0x02 => Some("aes192"),
+                0x03 => Some("aes256"),
+                _ => None,
+            };
+        }
+        rest = &rest[4 + len..];
+    }
+    None
+}
+
+// 비밀번호를 묻기 전에 ZIP이 암호화되어 있는지, 어떤 방식인지 확인하는 명령어
+// 중앙 디렉터리만 읽으므로 비밀번호가 필요 없으며, 폴더 항목은 세지 않습니다.
+// methods에 "zipcrypto"가 있으면 취약한 방식이므로 UI에서 경고할 수 있습니다.
+#[tauri::command(async)]
+fn get_zip_encryption_info(zip_path: String) -> Result<EncryptionInfo, CommandError> {
+    let file = File::open(&zip_path).map_err(|e| e.to_string())?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut methods = Vec::new();
+    let mut encrypted_entries = 0;
+    let mut total_entries = 0;
+    for i in 0..archive.len() {
+        let raw = archive.by_index_raw(i).map_err(|e| e.to_string())?;
+        if raw.is_dir() {
+            continue;
+        }
+        total_entries += 1;
+        if !raw.encrypted() {
+            continue;
+        }
+        encrypted_entries += 1;
+        let method = raw
+            .extra_data()
+            .and_then(aes_strength)
+            .unwrap_or("zipcrypto");
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+    Ok(EncryptionInfo {
+        encrypted: encrypted_entries > 0,
+        methods,
+        mixed: encrypted_entries > 0 && encrypted_entries < total_entries,
+        encrypted_entries,
+        total_entries,
+    })
 }
 
 #[tauri::command]
-fn open_file(path: String) -> Result<(), String> {
-    open::that(path).map_err(|e| e.to_string())
+fn open_file(path: String) -> Result<(), CommandError> {
+    Ok(open::that(path)?)
 }
 
 // 휴지통으로 이동 명령어
 #[tauri::command]
-fn delete_to_trash(paths: Vec<String>) -> Result<(), String> {
-    trash::delete_all(&paths).map_err(|e| e.to_string())
+fn delete_to_trash(paths: Vec<String>) -> Result<(), CommandError> {
+    Ok(trash::delete_all(&paths).map_err(|e| e.to_string())?)
 }
 
 #[tauri::command]
@@ -560,7 +4708,7 @@ fn get_available_drives() -> Vec<String> {
 }
 
 #[tauri::command]
-async fn read_directory(path: String) -> Result<Vec<DirectoryEntry>, String> {
+async fn read_directory(path: String) -> Result<Vec<DirectoryEntry>, CommandError> {
     let path_buf = PathBuf::from(&path);
     let entries = tauri::async_runtime::spawn_blocking(move || {
         let mut result = Vec::new();
@@ -598,7 +4746,7 @@ async fn read_directory(path: String) -> Result<Vec<DirectoryEntry>, String> {
 }
 
 #[tauri::command]
-async fn search_directory(path: String, query: String, use_regex: bool) -> Result<Vec<String>, String> {
+async fn search_directory(path: String, query: String, use_regex: bool) -> Result<Vec<String>, CommandError> {
     // CPU 집약적이거나 I/O 작업이 많을 수 있으므로 spawn_blocking 사용
     let results = tauri::async_runtime::spawn_blocking(move || {
         let mut matches = Vec::new();
@@ -638,7 +4786,7 @@ async fn search_directory(path: String, query: String, use_regex: bool) -> Resul
 }
 
 #[tauri::command]
-fn copy_files_to_clipboard(paths: Vec<String>) -> Result<(), String> {
+fn copy_files_to_clipboard(paths: Vec<String>) -> Result<(), CommandError> {
     #[cfg(target_os = "windows")]
     {
         use std::ffi::OsStr;
@@ -670,7 +4818,7 @@ fn copy_files_to_clipboard(paths: Vec<String>) -> Result<(), String> {
             let h_global = GlobalAlloc(GMEM_MOVEABLE, total_size).map_err(|e| e.to_string())?;
             let ptr = GlobalLock(h_global);
             if ptr.is_null() {
-                return Err("GlobalLock failed".to_string());
+                return Err("GlobalLock failed".into());
             }
 
             // --- 데이터를 쓰는 동안은 Lock 상태 유지 ---
@@ -694,13 +4842,13 @@ fn copy_files_to_clipboard(paths: Vec<String>) -> Result<(), String> {
                 // SetClipboardData에 핸들을 넘기면, 이후 해당 메모리의 소유권은 시스템이 가집니다.
                 if let Err(e) = SetClipboardData(15, handle) {
                     let _ = CloseClipboard();
-                    return Err(format!("SetClipboardData failed: {}", e));
+                    return Err(format!("SetClipboardData failed: {}", e).into());
                 }
                 let _ = CloseClipboard();
             } else {
                 // 클립보드 열기 실패 시 메모리 해제 고려가 필요할 수 있으나,
                 // 일반적으로 GlobalAlloc된 핸들은 시스템에 등록되지 않으면 직접 해제해야 합니다.
-                return Err("OpenClipboard failed".to_string());
+                return Err("OpenClipboard failed".into());
             }
         }
     }
@@ -708,7 +4856,7 @@ fn copy_files_to_clipboard(paths: Vec<String>) -> Result<(), String> {
 }
 
 #[tauri::command]
-fn get_files_from_clipboard() -> Result<Vec<String>, String> {
+fn get_files_from_clipboard() -> Result<Vec<String>, CommandError> {
     #[cfg(target_os = "windows")]
     {
         use windows::Win32::System::DataExchange::{CloseClipboard, GetClipboardData, OpenClipboard};
@@ -745,7 +4893,7 @@ fn get_files_from_clipboard() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-fn open_in_explorer(path: String) -> Result<(), String> {
+fn open_in_explorer(path: String) -> Result<(), CommandError> {
     #[cfg(target_os = "windows")]
     {
         use std::process::{Command, Stdio};
@@ -765,7 +4913,7 @@ fn open_in_explorer(path: String) -> Result<(), String> {
         if let Err(e) = result {
             let error_message = format!("Failed to spawn explorer.exe for path '{}': {}", path, e);
             println!("[ERROR] {}", error_message);
-            return Err(error_message);
+            return Err(error_message.into());
         }
         
         println!("[DEBUG] Successfully spawned explorer.exe for path: {}", path);
@@ -792,49 +4940,82 @@ fn get_license_info(app: tauri::AppHandle) -> license::LicenseInfo {
 }
 
 #[tauri::command]
-fn activate_license(app: tauri::AppHandle, email: String, code: String) -> Result<license::LicenseInfo, String> {
-    license::activate(&app, &email, &code)
+fn activate_license(app: tauri::AppHandle, email: String, code: String) -> Result<license::LicenseInfo, CommandError> {
+    Ok(license::activate(&app, &email, &code)?)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .setup(|app| {
+            let drives = load_index_drives(app.handle());
             let state = AppState {
-                mft: Arc::new(MftIndex::new("C:".to_string())),
+                mft: RwLock::new(
+                    drives
+                        .iter()
+                        .map(|d| (d.clone(), Arc::new(MftIndex::new(d.clone()))))
+                        .collect(),
+                ),
+                compress_cancel: CancelRegistry::default(),
+                pause: PauseRegistry::default(),
+                search_exclusions: RwLock::new(load_search_exclusions(app.handle())),
+                preview_dirs: Mutex::new(Vec::new()),
+                max_path_depth: AtomicUsize::new(DEFAULT_MAX_PATH_DEPTH),
             };
 
-            // 앱 시작 시 인덱스 로드 및 모니터링 시작
-            let index_clone = state.mft.clone();
-            let app_handle = app.handle().clone();
-            let index_path =
-                get_index_path(&app_handle).expect("Failed to get index path on setup");
-
-            // 파일 로드는 I/O 작업이므로 별도 스레드에서 처리
-            std::thread::spawn(move || {
-                if index_path.exists() {
-                    println!("Loading existing index from disk...");
-                    if let Ok((next_usn, journal_id)) = index_clone.load_from_disk(&index_path) {
-                        println!("Index loaded successfully. Starting USN journal monitoring...");
-
-                        // 모니터링 스레드 시작
-                        let monitor_index = index_clone.clone();
-                        let monitor_app_handle = app_handle.clone();
-                        std::thread::spawn(move || {
-                            monitor_index.monitor(next_usn, journal_id, move |changes| {
-                                let _ = monitor_app_handle.emit("file-changes", changes);
-                            });
-                        });
+            // 드라이브 하나만 인덱싱하던 버전의 인덱스 파일은 첫 번째 드라이브의 인덱스로 사용
+            if let (Ok(legacy), Ok(path)) = (
+                get_legacy_index_path(app.handle()),
+                get_index_path(app.handle(), &drives[0]),
+            ) {
+                if legacy.exists() && !path.exists() {
+                    let _ = fs::rename(&legacy, &path);
+                }
+            }
+
+            // 앱 시작 시 드라이브별로 인덱스 로드 및 모니터링 시작
+            for index_clone in state.indexes() {
+                let app_handle = app.handle().clone();
+                let index_path = get_index_path(&app_handle, &index_clone.drive_letter)
+                    .expect("Failed to get index path on setup");
+
+                // 파일 로드는 I/O 작업이므로 별도 스레드에서 처리
+                std::thread::spawn(move || {
+                    let drive = &index_clone.drive_letter;
+                    if index_path.exists() {
+                        println!("Loading existing index for {} from disk...", drive);
+                        set_index_state(&app_handle, &index_clone, IndexState::Building);
+                        match index_clone.load_from_disk(&index_path) {
+                            Ok((next_usn, journal_id)) => {
+                                println!("Index for {} loaded successfully. Starting USN journal monitoring...", drive);
 
-                        // 프론트엔드에 로드 완료 이벤트 전송
-                        let _ = app_handle.emit("index-ready", true);
+                                // 모니터링 스레드 시작
+                                start_monitor(app_handle.clone(), index_clone.clone(), next_usn, journal_id);
+
+                                // 프론트엔드에 로드 완료 이벤트 전송
+                                set_index_state(&app_handle, &index_clone, IndexState::Ready);
+                                let _ = app_handle.emit("index-ready", true);
+                            }
+                            Err(e) if e == INDEX_REBUILD_REQUIRED => {
+                                // 형식이 바뀐 인덱스 파일은 버리고 재인덱싱을 요청
+                                println!("Index file format for {} is outdated. Rebuild required.", drive);
+                                let _ = fs::remove_file(&index_path);
+                                set_index_state(&app_handle, &index_clone, IndexState::Stale);
+                                let _ = app_handle.emit(
+                                    "index-stale",
+                                    format!("{}: Index file format is outdated", drive),
+                                );
+                            }
+                            Err(_) => {
+                                println!("Failed to load index file for {}. Please re-index manually.", drive);
+                                set_index_state(&app_handle, &index_clone, IndexState::NotBuilt);
+                            }
+                        }
                     } else {
-                        println!("Failed to load index file. Please re-index manually.");
+                        println!("No index file found for {}. Please build the index.", drive);
                     }
-                } else {
-                    println!("No index file found. Please build the index.");
-                }
-            });
+                });
+            }
 
             app.manage(state);
             Ok(())
@@ -845,9 +5026,48 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             compress_files,
+            compress_buffers,
+            cancel_compression,
+            benchmark_compression,
+            estimate_password_strength,
+            pause_operation,
+            resume_operation,
+            create_sfx,
+            create_tar_gz,
+            gzip_file,
+            gunzip_file,
+            append_to_zip,
+            remove_from_zip,
+            rename_zip_entry,
+            recompress_zip,
+            generate_manifest,
+            get_index_drives,
+            get_index_mode,
+            get_index_state,
+            list_directory_from_index,
+            get_index_stats,
+            set_max_path_depth,
+            validate_index,
+            set_index_drives,
+            stop_monitoring,
+            get_search_exclusions,
+            set_search_exclusions,
             extract_zip,
             list_zip_contents,
+            list_zip_dir,
+            list_zip_page,
+            search_in_zip,
+            grep_in_zip,
+            read_zip_entry,
+            read_zip_entry_range,
+            extract_to_memory,
+            open_zip_entry,
             extract_zip_files,
+            get_zip_uncompressed_size,
+            check_extract_space,
+            verify_zip,
+            verify_zip_password,
+            get_zip_encryption_info,
             open_file,
             build_mft_index,
             search_mft,
@@ -862,6 +5082,844 @@ pub fn run() {
             activate_license
         ])
         // .invoke_handler(tauri::generate_handler![greet])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // 종료 시 open_zip_entry로 풀어둔 임시 파일 정리
+            if let tauri::RunEvent::Exit = event {
+                app.state::<AppState>().cleanup_preview_dirs();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use std::sync::atomic::AtomicUsize;
+    use tauri::test::{mock_app, MockRuntime};
+    use zip::write::SimpleFileOptions;
+
+    // 테스트마다 새로 만들고 끝나면 지우는 임시 폴더
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let path = std::env::temp_dir().join(format!(
+                "mhzipy-test-{}-{}-{}",
+                name,
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::SeqCst)
+            ));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn join(&self, name: &str) -> PathBuf {
+            self.0.join(name)
+        }
+
+        fn path_str(&self, name: &str) -> String {
+            self.join(name).to_string_lossy().into_owned()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    // 명령어를 직접 호출할 수 있도록 MockRuntime 앱과 창을 만듦
+    fn test_app() -> (tauri::App<MockRuntime>, Window<MockRuntime>) {
+        let app = mock_app();
+        app.manage(AppState {
+            mft: RwLock::new(BTreeMap::new()),
+            compress_cancel: CancelRegistry::default(),
+            pause: PauseRegistry::default(),
+            search_exclusions: RwLock::new(Vec::new()),
+            preview_dirs: Mutex::new(Vec::new()),
+            max_path_depth: AtomicUsize::new(DEFAULT_MAX_PATH_DEPTH),
+        });
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        let window = webview.as_ref().window();
+        (app, window)
+    }
+
+    // (이름, 내용) 목록으로 ZIP을 만듦 (이름이 '/'로 끝나면 폴더)
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])], options: SimpleFileOptions) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in entries {
+            if name.ends_with('/') {
+                zip.add_directory(*name, options).unwrap();
+            } else {
+                zip.start_file(*name, options).unwrap();
+                zip.write_all(data).unwrap();
+            }
+        }
+        zip.finish().unwrap();
+    }
+
+    // 모든 항목을 덮어쓰기로 해제 (나머지 옵션은 기본값)
+    fn extract_all(
+        app: &tauri::App<MockRuntime>,
+        window: &Window<MockRuntime>,
+        zip_path: &str,
+        target_dir: &str,
+        password: Option<&str>,
+        continue_on_error: bool,
+    ) -> Result<ExtractSummary, CommandError> {
+        extract_zip_files(
+            window.clone(),
+            app.state(),
+            zip_path.to_string(),
+            None,
+            target_dir.to_string(),
+            true,
+            password.map(str::to_string),
+            None,
+            Some(continue_on_error),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    // compress_files 인자 중 테스트에서 바꾸는 것만 모음 (나머지는 기본값)
+    #[derive(Default)]
+    struct CompressArgs {
+        split_size: Option<u64>,
+        comment: Option<String>,
+        deterministic: Option<bool>,
+    }
+
+    fn compress(
+        app: &tauri::App<MockRuntime>,
+        window: &Window<MockRuntime>,
+        paths: &[PathBuf],
+        target: &Path,
+        args: CompressArgs,
+    ) -> CompressSummary {
+        let paths = paths.iter().map(|p| p.to_string_lossy().into_owned());
+        compress_files(
+            window.clone(),
+            app.state(),
+            paths.collect(),
+            target.to_string_lossy().into_owned(),
+            None,
+            None,
+            None,
+            None,
+            args.split_size,
+            None,
+            None,
+            None,
+            args.comment,
+            None,
+            None,
+            None,
+            args.deterministic,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    // 압축되지 않는 의사 난수 데이터 (xorshift)
+    fn noise(len: usize) -> Vec<u8> {
+        let mut x: u32 = 0x9e37_79b9;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x as u8
+            })
+            .collect()
+    }
+
+    // ZIP 항목 이름 목록 (정렬)
+    fn zip_names(path: &Path) -> Vec<String> {
+        let archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        names
+    }
+
+    fn read_entry(path: &Path, name: &str, password: Option<&str>) -> Vec<u8> {
+        let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut data = Vec::new();
+        match password {
+            Some(p) => archive.by_name_decrypt(name, p.as_bytes()).unwrap(),
+            None => archive.by_name(name).unwrap(),
+        }
+        .read_to_end(&mut data)
+        .unwrap();
+        data
+    }
+
+    #[test]
+    fn safe_output_path_keeps_entries_inside_target() {
+        let dir = TempDir::new("safe-path");
+        let target = dir.join("out");
+        fs::create_dir_all(&target).unwrap();
+
+        let inside = safe_output_path(&target, "docs/./a.txt").unwrap();
+        assert_eq!(inside, target.join("docs").join("a.txt"));
+        for name in [
+            "../../evil.txt",
+            "docs/../../evil.txt",
+            "..\\evil.txt",
+            "/etc/passwd",
+            "\\evil.txt",
+            "C:\\evil.txt",
+            "C:/evil.txt",
+            "c:evil.txt",
+        ] {
+            let err = safe_output_path(&target, name).unwrap_err();
+            assert_eq!(err, format!("PATH_TRAVERSAL: {}", name));
+        }
+    }
+
+    #[test]
+    fn extract_rejects_zip_slip_entries() {
+        let dir = TempDir::new("zip-slip");
+        let zip_path = dir.path_str("slip.zip");
+        write_zip(
+            Path::new(&zip_path),
+            &[("good.txt", b"good"), ("../../evil.txt", b"evil")],
+            SimpleFileOptions::default(),
+        );
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        let (app, window) = test_app();
+
+        let err = extract_all(&app, &window, &zip_path, &dir.path_str("a/b"), None, false)
+            .err()
+            .unwrap();
+        assert_eq!(err.code, ErrorCode::PathTraversal);
+
+        let summary =
+            extract_all(&app, &window, &zip_path, &dir.path_str("a/b"), None, true).unwrap();
+        assert_eq!(fs::read(dir.join("a/b/good.txt")).unwrap(), b"good");
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].name, "../../evil.txt");
+        assert!(summary.failed[0].error.starts_with("PATH_TRAVERSAL"));
+        assert!(!dir.join("evil.txt").exists());
+        assert!(!dir.join("a/evil.txt").exists());
+    }
+
+    #[test]
+    fn append_folder_twice_with_each_policy() {
+        let dir = TempDir::new("append");
+        fs::create_dir_all(dir.join("src/docs")).unwrap();
+        fs::write(dir.join("src/docs/a.txt"), b"first").unwrap();
+        let zip_path = dir.join("archive.zip");
+        write_zip(
+            &zip_path,
+            &[("readme.txt", b"readme")],
+            SimpleFileOptions::default(),
+        );
+        let (app, window) = test_app();
+        let append = |on_conflict: Option<&str>| {
+            append_to_zip(
+                window.clone(),
+                app.state(),
+                zip_path.to_string_lossy().into_owned(),
+                vec![dir.path_str("src/docs")],
+                on_conflict.map(str::to_string),
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+        let expected = ["docs/", "docs/a.txt", "readme.txt"];
+
+        append(None).unwrap();
+        assert_eq!(zip_names(&zip_path), expected);
+        assert_eq!(append(None).unwrap_err().code, ErrorCode::FileExists);
+
+        fs::write(dir.join("src/docs/a.txt"), b"second").unwrap();
+        append(Some("skip")).unwrap();
+        assert_eq!(zip_names(&zip_path), expected);
+        assert_eq!(read_entry(&zip_path, "docs/a.txt", None), b"first");
+
+        append(Some("overwrite")).unwrap();
+        assert_eq!(zip_names(&zip_path), expected);
+        assert_eq!(read_entry(&zip_path, "docs/a.txt", None), b"second");
+        assert_eq!(read_entry(&zip_path, "readme.txt", None), b"readme");
+    }
+
+    #[test]
+    fn starting_an_operation_keeps_pending_cancel_of_another() {
+        let registry = CancelRegistry::default();
+        let running = registry.begin();
+        registry.cancel_all();
+        let next = registry.begin();
+        assert!(running.load(Ordering::SeqCst));
+        assert!(!next.load(Ordering::SeqCst));
+        drop(running);
+        drop(next);
+        assert!(registry.tokens.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn starting_or_cancelling_an_operation_keeps_another_paused() {
+        let cancels = CancelRegistry::default();
+        let pauses = PauseRegistry::default();
+        let extracting = pauses.begin(None);
+        pauses.set_paused(true);
+        let cancel = cancels.begin();
+        let compressing = pauses.begin(Some(&cancel));
+        assert!(extracting.state.lock().unwrap().paused);
+        assert!(!compressing.state.lock().unwrap().paused);
+
+        // 취소된 압축은 일시 정지 중이어도 wait()에서 빠져나오고, 해제 작업은 계속 멈춰 있음
+        pauses.set_paused(true);
+        cancels.cancel_all();
+        pauses.wake_all();
+        compressing.wait();
+        assert!(extracting.state.lock().unwrap().paused);
+        drop(compressing);
+        drop(extracting);
+        assert!(pauses.gates.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unpack_archive_decrypts_encrypted_entries() {
+        let dir = TempDir::new("unpack-aes");
+        let zip_path = dir.join("inner.zip");
+        let options = SimpleFileOptions::default().with_aes_encryption(zip::AesMode::Aes256, "pw");
+        write_zip(&zip_path, &[("secret.txt", b"secret")], options);
+        let (_app, window) = test_app();
+        let unpack = UnpackOptions {
+            password: Some("pw"),
+            encoding: None,
+            symlink_policy: SymlinkPolicy::Skip,
+            name_substitute: '_',
+        };
+        let mut guard = BombGuard::new(fs::metadata(&zip_path).unwrap().len(), None, None);
+        let mut summary = ExtractSummary::default();
+        let out = dir.join("out");
+        let extracted =
+            unpack_archive(&window, &zip_path, &out, &unpack, &mut guard, &mut summary).unwrap();
+        assert_eq!(extracted, vec![out.join("secret.txt")]);
+        assert_eq!(fs::read(out.join("secret.txt")).unwrap(), b"secret");
+    }
+
+    #[test]
+    fn split_parts_extract_from_any_part() {
+        let dir = TempDir::new("split");
+        let data = noise(200_000);
+        fs::write(dir.join("data.bin"), &data).unwrap();
+        let zip_path = dir.join("archive.zip");
+        let (app, window) = test_app();
+        let args = CompressArgs {
+            split_size: Some(split::MIN_SPLIT_SIZE),
+            ..Default::default()
+        };
+        compress(&app, &window, &[dir.join("data.bin")], &zip_path, args);
+
+        // 최종 .zip 없이 archive.zip.001, .002, ...만 남고 임시 .part 파일은 없어야 함
+        assert!(!zip_path.exists());
+        let parts: Vec<PathBuf> = (1..)
+            .map(|n| split::part_path(&zip_path, n))
+            .take_while(|p| p.is_file())
+            .collect();
+        assert!(parts.len() >= 3);
+        assert!(parts[0].ends_with("archive.zip.001"));
+        assert!(!split::temp_path(&parts[0]).exists());
+
+        // 조각을 이어 붙이면 그대로 일반 ZIP
+        let joined: Vec<u8> = parts.iter().flat_map(|p| fs::read(p).unwrap()).collect();
+        fs::write(dir.join("joined.zip"), joined).unwrap();
+        assert_eq!(read_entry(&dir.join("joined.zip"), "data.bin", None), data);
+
+        // 아무 조각이나 넘겨도 원래 ZIP 이름의 하위 폴더로 해제
+        extract_zip(
+            window.clone(),
+            app.state(),
+            parts[1].to_string_lossy().into_owned(),
+            dir.path_str("out"),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(fs::read(dir.join("out/archive/data.bin")).unwrap(), data);
+    }
+
+    #[test]
+    fn extract_mixed_encryption_with_one_password() {
+        let dir = TempDir::new("mixed-aes");
+        let zip_path = dir.join("mixed.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        zip.start_file("plain.txt", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"plain").unwrap();
+        let aes = SimpleFileOptions::default().with_aes_encryption(zip::AesMode::Aes256, "pw");
+        zip.start_file("secret.txt", aes).unwrap();
+        zip.write_all(b"secret").unwrap();
+        zip.finish().unwrap();
+
+        let (app, window) = test_app();
+        let (zip_str, out) = (dir.path_str("mixed.zip"), dir.path_str("out"));
+        let summary = extract_all(&app, &window, &zip_str, &out, Some("pw"), false).unwrap();
+        assert!(summary.failed.is_empty());
+        assert_eq!(fs::read(dir.join("out/plain.txt")).unwrap(), b"plain");
+        assert_eq!(fs::read(dir.join("out/secret.txt")).unwrap(), b"secret");
+    }
+
+    #[test]
+    fn zstd_round_trips_a_multi_megabyte_file() {
+        let dir = TempDir::new("zstd");
+        let zip_path = dir.join("zstd.zip");
+        let mut data = noise(1 << 20);
+        data.extend(b"MHZipy zstd ".repeat(256 * 1024));
+        let options = build_file_options(Some("zstd"), None, None, None).unwrap();
+        write_zip(&zip_path, &[("big.bin", &data)], options);
+
+        let mut archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        let entry = archive.by_name("big.bin").unwrap();
+        assert_eq!(entry.compression(), zip::CompressionMethod::Zstd);
+        assert!(entry.compressed_size() < entry.size());
+        assert_eq!(read_entry(&zip_path, "big.bin", None), data);
+    }
+
+    #[test]
+    fn extract_bzip2_entry() {
+        let dir = TempDir::new("bzip2");
+        let data = b"bzip2 entry\n".repeat(1000);
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Bzip2);
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("bz.txt", options).unwrap();
+        zip.write_all(&data).unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+        fs::write(dir.join("bz.zip"), bytes).unwrap();
+
+        let (app, window) = test_app();
+        let (zip_str, out) = (dir.path_str("bz.zip"), dir.path_str("out"));
+        extract_all(&app, &window, &zip_str, &out, None, false).unwrap();
+        assert_eq!(fs::read(dir.join("out/bz.txt")).unwrap(), data);
+    }
+
+    #[test]
+    fn decode_entry_name_uses_the_utf8_flag() {
+        // 같은 길이의 ASCII 이름(UTF-8 플래그 없음)으로 쓴 뒤 EUC-KR 바이트로 바꿔 레거시 ZIP을 만듦
+        let (euc_kr, _, _) = encoding_rs::EUC_KR.encode("한글.txt");
+        let options = SimpleFileOptions::default();
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("XXXX.txt", options).unwrap();
+        zip.start_file("한글2.txt", options).unwrap();
+        let mut bytes = zip.finish().unwrap().into_inner();
+        let mut from = 0;
+        while let Some(at) = bytes[from..].windows(8).position(|w| w == b"XXXX.txt") {
+            let at = from + at;
+            bytes[at..at + 8].copy_from_slice(&euc_kr);
+            from = at + 8;
+        }
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.by_index_raw(0).unwrap().name_raw(), &euc_kr[..]);
+        let names = entry_names(&mut archive, Some(encoding_rs::EUC_KR)).unwrap();
+        // 플래그가 있는 UTF-8 이름은 인코딩을 지정해도 그대로 둠
+        assert_eq!(names, ["한글.txt", "한글2.txt"]);
+    }
+
+    #[test]
+    fn hangul_names_compress_and_list_back() {
+        let dir = TempDir::new("hangul");
+        let folder = dir.join("문서");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("보고서 최종.txt"), b"hangul").unwrap();
+        let zip_path = dir.join("한글.zip");
+        let (app, window) = test_app();
+        compress(&app, &window, &[folder], &zip_path, Default::default());
+
+        let contents = list_zip_contents(dir.path_str("한글.zip"), None, None).unwrap();
+        let mut names: Vec<&str> = contents.entries.iter().map(|e| e.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["문서/", "문서/보고서 최종.txt"]);
+        let data = read_entry(&zip_path, "문서/보고서 최종.txt", None);
+        assert_eq!(data, b"hangul");
+    }
+
+    #[test]
+    fn archive_comment_round_trips() {
+        let dir = TempDir::new("comment");
+        fs::write(dir.join("a.txt"), b"a").unwrap();
+        let zip_path = dir.join("comment.zip");
+        let (app, window) = test_app();
+        let comment = "백업 2026-10-15\nbuild 42";
+        let args = CompressArgs {
+            comment: Some(comment.to_string()),
+            ..Default::default()
+        };
+        compress(&app, &window, &[dir.join("a.txt")], &zip_path, args);
+
+        let contents = list_zip_contents(dir.path_str("comment.zip"), None, None).unwrap();
+        assert_eq!(contents.comment.as_deref(), Some(comment));
+    }
+
+    #[test]
+    fn deterministic_output_is_byte_identical() {
+        let dir = TempDir::new("deterministic");
+        let tree = dir.join("tree");
+        fs::create_dir_all(tree.join("sub")).unwrap();
+        for i in 0..8 {
+            let text = format!("file {}", i).repeat(500);
+            fs::write(tree.join(format!("sub/{}.txt", i)), text).unwrap();
+        }
+        fs::write(tree.join("noise.bin"), noise(100_000)).unwrap();
+        let (app, window) = test_app();
+        let paths = [tree.clone()];
+        let (first, second) = (dir.join("a.zip"), dir.join("b.zip"));
+        let deterministic = || CompressArgs {
+            deterministic: Some(true),
+            ..Default::default()
+        };
+        compress(&app, &window, &paths, &first, deterministic());
+
+        // 수정 시간이 바뀌어도 결과는 같아야 함
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        File::options()
+            .write(true)
+            .open(tree.join("noise.bin"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        compress(&app, &window, &paths, &second, deterministic());
+
+        assert!(fs::read(first).unwrap() == fs::read(second).unwrap());
+    }
+
+    #[test]
+    fn overwrite_check_leaves_zip_slip_entries_to_continue_on_error() {
+        let dir = TempDir::new("slip-no-overwrite");
+        let zip_path = dir.join("slip.zip");
+        let entries: &[(&str, &[u8])] = &[("good.txt", b"good"), ("../evil.txt", b"evil")];
+        write_zip(&zip_path, entries, SimpleFileOptions::default());
+        let (app, window) = test_app();
+
+        // overwrite가 false면 먼저 기존 파일을 확인하지만, 경로 검사에 걸린 항목은 실패 목록으로 넘김
+        let summary = extract_zip_files(
+            window.clone(),
+            app.state(),
+            dir.path_str("slip.zip"),
+            None,
+            dir.path_str("out"),
+            false,
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(fs::read(dir.join("out/good.txt")).unwrap(), b"good");
+        assert_eq!(summary.failed.len(), 1);
+        assert!(summary.failed[0].error.starts_with("PATH_TRAVERSAL"));
+        assert!(!dir.join("evil.txt").exists());
+    }
+
+    #[test]
+    fn rename_zip_entry_rejects_names_outside_the_archive() {
+        let dir = TempDir::new("rename");
+        let zip_path = dir.join("rename.zip");
+        write_zip(&zip_path, &[("a.txt", b"a")], SimpleFileOptions::default());
+        let zip_str = dir.path_str("rename.zip");
+
+        for name in [
+            "../a.txt",
+            "docs/../../a.txt",
+            "/a.txt",
+            "\\a.txt",
+            "C:/a.txt",
+            "C:a.txt",
+        ] {
+            let err = rename_zip_entry(zip_str.clone(), "a.txt".into(), name.into()).unwrap_err();
+            assert_eq!(err.code, ErrorCode::PathTraversal, "{}", name);
+        }
+        assert_eq!(zip_names(&zip_path), ["a.txt"]);
+
+        let renamed = rename_zip_entry(zip_str, "a.txt".into(), "docs\\b.txt".into()).unwrap();
+        assert_eq!(renamed, 1);
+        assert_eq!(zip_names(&zip_path), ["docs/b.txt"]);
+    }
+
+    #[test]
+    fn renamed_entry_moves_files_and_folder_contents() {
+        let renamed = renamed_entry("a.txt", "a.txt", "b.txt");
+        assert_eq!(renamed.as_deref(), Some("b.txt"));
+        assert_eq!(renamed_entry("other.txt", "a.txt", "b.txt"), None);
+        // 폴더는 "docs"와 "docs/" 어느 쪽으로 지정해도 그 아래 항목의 앞쪽 경로만 바뀝니다.
+        let moved = renamed_entry("docs/x.txt", "docs/", "files");
+        assert_eq!(moved.as_deref(), Some("files/x.txt"));
+        let moved = renamed_entry("docs/sub/x.txt", "docs", "files/");
+        assert_eq!(moved.as_deref(), Some("files/sub/x.txt"));
+        assert_eq!(renamed_entry("docs2/x.txt", "docs", "files"), None);
+    }
+
+    #[test]
+    fn sanitize_entry_name_replaces_characters_windows_rejects() {
+        let sanitized = sanitize_entry_name("a<b>/c:d.txt", '_');
+        assert_eq!(sanitized, "a_b_/c_d.txt");
+        assert_eq!(sanitize_entry_name("docs\\a|b", '_'), "docs/a_b");
+        // 끝의 점/공백은 지우고, 점만으로 된 요소는 substitute 하나로 바꿉니다.
+        assert_eq!(sanitize_entry_name("dir. /file ", '_'), "dir/file");
+        assert_eq!(sanitize_entry_name(".../a.txt", '_'), "_/a.txt");
+        // ".."은 safe_output_path가 거르도록 그대로 둡니다.
+        assert_eq!(sanitize_entry_name("../x?.txt", '_'), "../x_.txt");
+        let unchanged = sanitize_entry_name("a/b.txt", '_');
+        assert!(matches!(unchanged, std::borrow::Cow::Borrowed("a/b.txt")));
+    }
+
+    #[test]
+    fn buffer_entry_name_normalizes_separators_and_rejects_parent_paths() {
+        assert_eq!(buffer_entry_name("\\docs\\a.txt").unwrap(), "docs/a.txt");
+        assert_eq!(buffer_entry_name("/a.txt").unwrap(), "a.txt");
+        for name in ["", "/", "a/../b.txt", "..\\b.txt"] {
+            assert!(buffer_entry_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn normalize_base_prefix_joins_parts_with_a_trailing_slash() {
+        assert_eq!(normalize_base_prefix("").unwrap(), "");
+        assert_eq!(normalize_base_prefix("/").unwrap(), "");
+        let prefix = normalize_base_prefix("\\backup\\\\2024/").unwrap();
+        assert_eq!(prefix, "backup/2024/");
+        for prefix in ["a/../b", "./a", "C:/backup", "a\u{1}b"] {
+            assert!(normalize_base_prefix(prefix).is_err(), "{prefix:?}");
+        }
+    }
+
+    #[test]
+    fn aes_strength_finds_the_aes_header_among_extra_fields() {
+        // 다른 extra field(0x000a) 뒤에 AES 헤더: 벤더 버전 2, "AE", 강도, 실제 압축 방식 8
+        let mut extra = vec![0x0a, 0x00, 0x04, 0x00, 0, 0, 0, 0];
+        extra.extend([
+            0x01, 0x99, 0x07, 0x00, 0x02, 0x00, b'A', b'E', 0x03, 0x08, 0x00,
+        ]);
+        assert_eq!(aes_strength(&extra), Some("aes256"));
+        extra[16] = 0x01;
+        assert_eq!(aes_strength(&extra), Some("aes128"));
+        assert_eq!(aes_strength(&extra[..8]), None);
+        assert_eq!(aes_strength(&[]), None);
+        // 길이가 실제 데이터보다 긴 잘린 필드
+        assert_eq!(aes_strength(&[0x01, 0x99, 0x07, 0x00, 0x02]), None);
+    }
+
+    #[test]
+    fn create_tar_gz_checks_and_replaces_an_existing_target() {
+        let dir = TempDir::new("tar-gz-overwrite");
+        fs::write(dir.join("a.txt"), b"hello").unwrap();
+        let target = dir.join("out.tar.gz");
+        fs::write(&target, b"original").unwrap();
+        let (app, window) = test_app();
+        let create = |overwrite| {
+            create_tar_gz(
+                window.clone(),
+                app.state(),
+                vec![dir.path_str("a.txt")],
+                target.to_string_lossy().into_owned(),
+                None,
+                None,
+                None,
+                overwrite,
+            )
+        };
+
+        let err = create(None).err().unwrap();
+        assert_eq!(err.code, ErrorCode::FileExists);
+        assert_eq!(fs::read(&target).unwrap(), b"original");
+
+        create(Some(true)).unwrap();
+        assert_eq!(fs::read(&target).unwrap()[..2], [0x1f, 0x8b]);
+        assert!(!split::temp_path(&target).exists());
+    }
+
+    #[test]
+    fn failed_gunzip_keeps_the_file_it_would_overwrite() {
+        let dir = TempDir::new("gunzip-overwrite");
+        let (src, gz) = (dir.path_str("a.txt"), dir.path_str("a.txt.gz"));
+        let dest = dir.join("out.txt");
+        fs::write(&src, b"hello").unwrap();
+        fs::write(&dest, b"original").unwrap();
+        let (app, window) = test_app();
+        gzip_file(window.clone(), app.state(), src, gz.clone(), None, None).unwrap();
+
+        // 손상된 .gz로 덮어쓰기가 실패해도 기존 파일은 그대로 남음
+        let broken = dir.path_str("broken.gz");
+        fs::write(&broken, b"not gzip").unwrap();
+        let dest_str = dest.to_string_lossy().into_owned();
+        assert!(gunzip_file(window.clone(), broken, dest_str.clone(), Some(true)).is_err());
+        assert_eq!(fs::read(&dest).unwrap(), b"original");
+        assert!(!split::temp_path(&dest).exists());
+
+        assert_eq!(gunzip_file(window, gz, dest_str, Some(true)).unwrap(), 5);
+        assert_eq!(fs::read(&dest).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn failed_recompress_keeps_the_file_it_would_overwrite() {
+        let dir = TempDir::new("recompress-overwrite");
+        let src = dir.join("src.zip");
+        let options = SimpleFileOptions::default().with_aes_encryption(zip::AesMode::Aes256, "pw");
+        write_zip(&src, &[("a.txt", b"hello")], options);
+        let dest = dir.join("dest.zip");
+        fs::write(&dest, b"original").unwrap();
+        let (app, window) = test_app();
+        let recompress = |password: &str| {
+            recompress_zip(
+                window.clone(),
+                app.state(),
+                src.to_string_lossy().into_owned(),
+                dest.to_string_lossy().into_owned(),
+                Some("stored".into()),
+                None,
+                Some(password.into()),
+                None,
+                None,
+                Some(true),
+            )
+        };
+
+        assert!(recompress("wrong").is_err());
+        assert_eq!(fs::read(&dest).unwrap(), b"original");
+        assert!(!split::temp_path(&dest).exists());
+
+        recompress("pw").unwrap();
+        assert_eq!(read_entry(&dest, "a.txt", None), b"hello");
+    }
+
+    #[test]
+    fn sfx_create_keeps_the_existing_output_on_failure() {
+        let dir = TempDir::new("sfx-overwrite");
+        let stub = dir.join("stub.exe");
+        fs::write(&stub, b"MZ stub").unwrap();
+        let output = dir.join("out.exe");
+        fs::write(&output, b"original").unwrap();
+
+        let not_zip = dir.join("not.zip");
+        fs::write(&not_zip, b"not a zip").unwrap();
+        assert!(sfx::create(&stub, &not_zip, &output).is_err());
+        assert_eq!(fs::read(&output).unwrap(), b"original");
+        assert!(!split::temp_path(&output).exists());
+
+        let zip_path = dir.join("a.zip");
+        let options = SimpleFileOptions::default();
+        write_zip(&zip_path, &[("a.txt", b"hello")], options);
+        sfx::create(&stub, &zip_path, &output).unwrap();
+        assert!(fs::read(&output).unwrap().starts_with(b"MZ stub"));
+        assert_eq!(read_entry(&output, "a.txt", None), b"hello");
+    }
+
+    #[test]
+    fn rejected_extraction_leaves_no_resume_log() {
+        let dir = TempDir::new("resume-preflight");
+        let zip_path = dir.join("a.zip");
+        let options = SimpleFileOptions::default();
+        write_zip(&zip_path, &[("a.txt", b"new")], options);
+        fs::create_dir_all(dir.join("out")).unwrap();
+        fs::write(dir.join("out/a.txt"), b"old").unwrap();
+        let (app, window) = test_app();
+
+        let err = extract_zip_files(
+            window.clone(),
+            app.state(),
+            zip_path.to_string_lossy().into_owned(),
+            None,
+            dir.path_str("out"),
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.code, ErrorCode::FileExists);
+        assert!(!resume::path_for(&dir.join("out"), &zip_path).exists());
+        assert_eq!(fs::read(dir.join("out/a.txt")).unwrap(), b"old");
+    }
+
+    #[test]
+    fn append_with_overwrite_keeps_the_archive_comment() {
+        let dir = TempDir::new("append-comment");
+        fs::write(dir.join("a.txt"), b"new").unwrap();
+        let zip_path = dir.join("archive.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        zip.start_file("a.txt", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"old").unwrap();
+        zip.set_comment("keep me");
+        zip.finish().unwrap();
+        let (app, window) = test_app();
+
+        append_to_zip(
+            window,
+            app.state(),
+            zip_path.to_string_lossy().into_owned(),
+            vec![dir.path_str("a.txt")],
+            Some("overwrite".into()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(read_entry(&zip_path, "a.txt", None), b"new");
+        let archive = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        assert_eq!(archive.comment(), b"keep me");
+    }
 }
@@ -0,0 +1,45 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+// ZIP 항목별 SHA-256 목록 (<zip 경로>.sha256)
+//
+// 한 줄에 "<16진수 해시>  <ZIP 내부 경로>" 형식으로 기록하므로, 압축을 푼 폴더에서
+// `sha256sum -c`로도 검사할 수 있습니다. 해시는 압축 전 원본 내용 기준이며 폴더 항목은 기록하지 않습니다.
+
+#[derive(Default)]
+pub struct Manifest {
+    entries: Vec<(String, String)>,
+}
+
+impl Manifest {
+    pub fn push(&mut self, name: &str, hasher: Sha256) {
+        self.entries.push((name.to_string(), to_hex(&hasher.finalize())));
+    }
+
+    // 이미 계산된 16진수 해시를 추가 (병렬 압축에서 스레드별로 계산한 값)
+    pub fn push_hex(&mut self, name: &str, hex: String) {
+        self.entries.push((name.to_string(), hex));
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        for (name, hash) in &self.entries {
+            writeln!(out, "{}  {}", hash, name)?;
+        }
+        out.flush()
+    }
+}
+
+// ZIP 옆에 만들 목록 파일 경로 (archive.zip -> archive.zip.sha256)
+pub fn path_for(zip_path: &Path) -> PathBuf {
+    let mut name = zip_path.as_os_str().to_owned();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -1,22 +1,35 @@
 use dashmap::DashMap;
 use rayon::prelude::*;
 use std::mem::size_of;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
-use std::sync::RwLock;
-use regex::RegexBuilder;
+use std::io::{BufReader, BufWriter, Write};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use regex::{Regex, RegexBuilder};
 use windows::core::PCWSTR;
-use windows::Win32::Foundation::{CloseHandle, ERROR_HANDLE_EOF, GENERIC_READ, HANDLE};
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_HANDLE_EOF, ERROR_JOURNAL_DELETE_IN_PROGRESS, ERROR_JOURNAL_ENTRY_DELETED,
+    ERROR_JOURNAL_NOT_ACTIVE, GENERIC_READ, HANDLE,
+};
 use windows::Win32::Storage::FileSystem::{
-    CreateFileW, FILE_ATTRIBUTE_DIRECTORY, FILE_FLAG_BACKUP_SEMANTICS, FILE_SHARE_READ,
-    FILE_SHARE_WRITE, OPEN_EXISTING,
+    CreateFileW, GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_ATTRIBUTE_DIRECTORY,
+    FILE_FLAG_BACKUP_SEMANTICS, FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE,
+    OPEN_EXISTING,
 };
 use windows::Win32::System::Ioctl::{
     FSCTL_ENUM_USN_DATA, FSCTL_READ_USN_JOURNAL, FSCTL_QUERY_USN_JOURNAL, MFT_ENUM_DATA_V0,
-    READ_USN_JOURNAL_DATA_V0, USN_JOURNAL_DATA_V0, USN_REASON_FILE_CREATE,
-    USN_REASON_FILE_DELETE, USN_REASON_RENAME_NEW_NAME, USN_REASON_RENAME_OLD_NAME,
-    USN_RECORD_COMMON_HEADER, USN_RECORD_V2,
+    READ_USN_JOURNAL_DATA_V0, USN_JOURNAL_DATA_V0, USN_REASON_BASIC_INFO_CHANGE,
+    USN_REASON_CLOSE, USN_REASON_COMPRESSION_CHANGE, USN_REASON_DATA_EXTEND,
+    USN_REASON_DATA_OVERWRITE, USN_REASON_DATA_TRUNCATION,
+    USN_REASON_DESIRED_STORAGE_CLASS_CHANGE, USN_REASON_EA_CHANGE, USN_REASON_ENCRYPTION_CHANGE,
+    USN_REASON_FILE_CREATE, USN_REASON_FILE_DELETE, USN_REASON_HARD_LINK_CHANGE,
+    USN_REASON_INDEXABLE_CHANGE, USN_REASON_INTEGRITY_CHANGE, USN_REASON_NAMED_DATA_EXTEND,
+    USN_REASON_NAMED_DATA_OVERWRITE, USN_REASON_NAMED_DATA_TRUNCATION,
+    USN_REASON_OBJECT_ID_CHANGE, USN_REASON_RENAME_NEW_NAME, USN_REASON_RENAME_OLD_NAME,
+    USN_REASON_REPARSE_POINT_CHANGE, USN_REASON_SECURITY_CHANGE, USN_REASON_STREAM_CHANGE,
+    USN_REASON_TRANSACTED_CHANGE, USN_RECORD_COMMON_HEADER, USN_RECORD_V2,
 };
 use windows::Win32::System::IO::DeviceIoControl;
 
@@ -26,6 +39,15 @@ pub struct FileEntry {
     pub parent_frn: u64,
     pub name: String,
     pub is_dir: bool,
+    // USN 레코드에는 파일 크기가 없으므로, 크기 필터 검색 시 실제 파일에서 조회해 채웁니다.
+    pub size: Option<u64>,
+    // 마지막 수정 시각 (unix millis). USN 레코드의 TimeStamp가 없으면 크기와 함께 조회합니다.
+    pub modified: Option<i64>,
+}
+
+// FILETIME(1601-01-01 기준 100ns 단위)을 unix millis로 변환 (0이면 값 없음)
+fn filetime_to_millis(filetime: i64) -> Option<i64> {
+    (filetime > 0).then(|| filetime / 10_000 - 11_644_473_600_000)
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -33,20 +55,730 @@ struct PersistentData {
     entries: Vec<(u64, FileEntry)>,
     next_usn: i64,
     journal_id: u64,
+    // 폴더 탐색으로 만든 인덱스인지 (USN 모니터링 불가)
+    walk_based: bool,
+}
+
+// 인덱스 파일 형식 버전 (FileEntry/PersistentData 구조가 바뀌면 올립니다)
+// 버전 3부터는 헤더 뒤의 본문을 zstd로 압축합니다 (버전 1, 2는 압축 없음).
+const INDEX_MAGIC: [u8; 4] = *b"MHZI";
+const INDEX_VERSION: u32 = 3;
+const INDEX_ZSTD_LEVEL: i32 = 3;
+
+// 인덱스 파일을 읽을 수 없어 다시 만들어야 할 때 load_from_disk가 반환하는 오류
+pub const INDEX_REBUILD_REQUIRED: &str = "INDEX_REBUILD_REQUIRED";
+
+// 인덱스 파일 맨 앞에 기록되는 헤더
+#[derive(serde::Serialize, serde::Deserialize)]
+struct IndexHeader {
+    magic: [u8; 4],
+    version: u32,
+}
+
+// 버전 1 형식 (walk_based 없음, 항상 MFT 기반)
+#[derive(serde::Deserialize)]
+struct PersistentDataV1 {
+    entries: Vec<(u64, FileEntry)>,
+    next_usn: i64,
+    journal_id: u64,
+}
+
+impl From<PersistentDataV1> for PersistentData {
+    fn from(v1: PersistentDataV1) -> Self {
+        PersistentData {
+            entries: v1.entries,
+            next_usn: v1.next_usn,
+            journal_id: v1.journal_id,
+            walk_based: false,
+        }
+    }
+}
+
+// 버전 헤더가 없던 초기 형식 (크기/수정 시각 없음)
+#[derive(serde::Deserialize)]
+struct LegacyFileEntry {
+    parent_frn: u64,
+    name: String,
+    is_dir: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct LegacyPersistentData {
+    entries: Vec<(u64, LegacyFileEntry)>,
+    next_usn: i64,
+    journal_id: u64,
+}
+
+impl From<LegacyPersistentData> for PersistentData {
+    fn from(legacy: LegacyPersistentData) -> Self {
+        PersistentData {
+            entries: legacy
+                .entries
+                .into_iter()
+                .map(|(frn, e)| {
+                    let entry = FileEntry {
+                        parent_frn: e.parent_frn,
+                        name: e.name,
+                        is_dir: e.is_dir,
+                        size: None,
+                        modified: None,
+                    };
+                    (frn, entry)
+                })
+                .collect(),
+            next_usn: legacy.next_usn,
+            journal_id: legacy.journal_id,
+            walk_based: false,
+        }
+    }
+}
+
+// 검색 방식
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchMode {
+    // 공백으로 나눈 단어가 모두 포함되면 일치 ("..."로 묶은 부분은 공백 포함 그대로 검색)
+    Substring,
+    // 검색어 전체를 하나의 부분 문자열로 검색 (단어로 나누지 않음)
+    Literal,
+    Glob,
+    Regex,
+    // 오타 허용 검색 (편집 거리 기반, 점수순 정렬)
+    Fuzzy,
+}
+
+impl SearchMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "substring" => Ok(SearchMode::Substring),
+            "literal" => Ok(SearchMode::Literal),
+            "glob" => Ok(SearchMode::Glob),
+            "regex" => Ok(SearchMode::Regex),
+            "fuzzy" => Ok(SearchMode::Fuzzy),
+            other => Err(format!("Unknown search mode: {}", other)),
+        }
+    }
+
+    // 검색어를 정규식으로 컴파일 (Substring, Literal, Fuzzy는 None)
+    // case_sensitive가 아니면 대소문자를 구분하지 않습니다.
+    pub fn compile(self, query: &str, case_sensitive: bool) -> Result<Option<Regex>, String> {
+        let pattern = match self {
+            SearchMode::Substring | SearchMode::Literal | SearchMode::Fuzzy => return Ok(None),
+            SearchMode::Glob => glob_to_regex(query),
+            SearchMode::Regex => query.to_string(),
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .map(Some)
+            .map_err(|e| format!("Invalid search pattern: {}", e))
+    }
+}
+
+// 검색 결과 정렬 기준 (큰 파일 / 최근 파일 순)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchSort {
+    Size,
+    Modified,
+}
+
+impl SearchSort {
+    pub fn parse(sort: &str) -> Result<Self, String> {
+        match sort {
+            "size" => Ok(SearchSort::Size),
+            "modified" => Ok(SearchSort::Modified),
+            other => Err(format!("Unknown search sort: {}", other)),
+        }
+    }
+}
+
+// 검색 결과 항목 (score는 0-100, 퍼지 검색 외에는 항상 100)
+#[derive(Clone, Debug)]
+pub struct SearchHit {
+    pub path: PathBuf,
+    pub score: u32,
+    // 결과가 나온 인덱스의 드라이브 (예: "D:")
+    pub drive: String,
+    // 정렬 기준 값 (여러 드라이브의 결과를 합칠 때도 한 드라이브 안과 같은 순서로 정렬)
+    rank: u8,
+    size: u64,
+    modified: i64,
+    frn: u64,
+}
+
+// 검색 결과에서 기본으로 숨기는 시스템 경로
+// 드라이브 루트 기준 경로이며 폴더 단위로 비교합니다 (대소문자 무시, "*"는 임의의 폴더 하나).
+// 이 경로의 항목도 인덱스에는 남아 있으므로 경로 재구성에는 영향이 없습니다.
+pub const DEFAULT_SEARCH_EXCLUSIONS: &[&str] = &[
+    "$Recycle.Bin",
+    "System Volume Information",
+    "Windows/WinSxS",
+    "Windows/Temp",
+    "Users/*/AppData/Local/Temp",
+];
+
+// 이름 외의 검색 조건 (확장자, 크기 범위, 수정 시각 범위, 제외 경로)
+// 이 조건 중 하나라도 지정되면 폴더는 결과에서 제외됩니다.
+// 정렬 기준이 없으면 크기 조건이 있을 때 크기순으로 정렬합니다.
+#[derive(Clone, Debug, Default)]
+pub struct SearchFilter {
+    pub ext: Option<String>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub modified_after: Option<i64>,
+    pub modified_before: Option<i64>,
+    pub sort: Option<SearchSort>,
+    // 결과에서 제외할 경로 (DEFAULT_SEARCH_EXCLUSIONS와 같은 형식)
+    pub exclude_paths: Vec<String>,
+    // 이름의 대소문자를 구분해 검색 (확장자 필터와 제외 경로는 항상 구분하지 않음)
+    pub case_sensitive: bool,
+}
+
+impl SearchFilter {
+    fn has_size_range(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    fn has_date_range(&self) -> bool {
+        self.modified_after.is_some() || self.modified_before.is_some()
+    }
+
+    fn is_active(&self) -> bool {
+        self.ext.is_some() || self.has_size_range() || self.has_date_range()
+    }
+
+    fn effective_sort(&self) -> Option<SearchSort> {
+        self.sort
+            .or_else(|| self.has_size_range().then_some(SearchSort::Size))
+    }
+
+    // 검색 결과 정렬 순서
+    // 경로를 재구성하기 전에 정렬할 수 있도록 경로는 쓰지 않습니다. 페이지를 나눠 요청해도 순서가 같도록
+    // 마지막 기준은 FRN(드라이브 안에서 고유)과, 여러 드라이브의 결과를 합칠 때의 드라이브입니다.
+    fn compare_hits(&self, a: &SearchHit, b: &SearchHit) -> std::cmp::Ordering {
+        let order = match self.effective_sort() {
+            Some(SearchSort::Size) => b.size.cmp(&a.size),
+            Some(SearchSort::Modified) => b.modified.cmp(&a.modified),
+            // 관련성 순: 등급, 퍼지 점수가 높은 순
+            None => b.rank.cmp(&a.rank).then(b.score.cmp(&a.score)),
+        };
+        order
+            .then(a.frn.cmp(&b.frn))
+            .then_with(|| a.drive.cmp(&b.drive))
+    }
+
+    // 크기/시각 조회가 필요한지 (필터링 또는 정렬용)
+    fn needs_metadata(&self) -> bool {
+        self.has_size_range() || self.has_date_range() || self.sort.is_some()
+    }
+
+    fn metadata_matches(&self, size: u64, modified: i64) -> bool {
+        self.min_size.map_or(true, |min| size >= min)
+            && self.max_size.map_or(true, |max| size <= max)
+            && self.modified_after.map_or(true, |after| modified >= after)
+            && self.modified_before.map_or(true, |before| modified <= before)
+    }
+}
+
+// 경로의 폴더/파일 이름 목록 (소문자, 드라이브 루트 제외)
+fn lowercase_parts(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+// 부모 폴더 경로(parent_parts) 아래의 name 항목이 제외 규칙 중 하나로 시작하는지 확인
+// (규칙과 parent_parts, name은 모두 소문자)
+fn is_excluded(parent_parts: &[String], name: &str, exclusions: &[Vec<String>]) -> bool {
+    exclusions.iter().any(|rule| {
+        let parts = parent_parts.iter().map(String::as_str).chain([name]);
+        rule.len() <= parent_parts.len() + 1
+            && rule.iter().zip(parts).all(|(r, p)| r == "*" || r == p)
+    })
+}
+
+// 퍼지 검색에서 허용하는 최대 편집 거리 (짧은 검색어는 오타를 적게 허용해 모든 이름과 일치하지 않도록 함)
+fn fuzzy_max_distance(query_len: usize) -> usize {
+    match query_len {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+// 검색어와 이름의 일부 구간 사이의 최소 편집 거리 (Levenshtein, 이름 안의 시작 위치는 자유)
+// 거리가 0이 되면 더 볼 필요가 없으므로 바로 반환하고, max를 넘으면 None을 반환합니다.
+fn fuzzy_distance(query: &[char], name: &str, max: usize) -> Option<usize> {
+    let m = query.len();
+    let mut prev: Vec<usize> = (0..=m).collect();
+    let mut cur = vec![0; m + 1];
+    let mut best = m;
+    for c in name.chars() {
+        // cur[0] = 0: 이름의 어느 위치에서 시작해도 비용 없음
+        for i in 1..=m {
+            let substitute = prev[i - 1] + usize::from(query[i - 1] != c);
+            cur[i] = substitute.min(prev[i] + 1).min(cur[i - 1] + 1);
+        }
+        best = best.min(cur[m]);
+        if best == 0 {
+            return Some(0);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    (best <= max).then_some(best)
+}
+
+// 편집 거리를 0-100 점수로 변환 (100이면 검색어가 그대로 포함됨)
+fn fuzzy_score(distance: usize, query_len: usize) -> u32 {
+    if query_len == 0 {
+        return 100;
+    }
+    (100 - distance.min(query_len) * 100 / query_len) as u32
+}
+
+// 이름 일치 결과
+// rank는 관련성 등급 (3: 이름 또는 확장자를 뺀 이름이 검색어와 같음, 2: 검색어로 시작, 1: 검색어를 포함하거나
+// glob/정규식과 일치, 0: 퍼지 일치), score는 0-100 점수 (퍼지 일치가 아니면 100)
+#[derive(Clone, Copy, Debug)]
+pub struct NameMatch {
+    pub score: u32,
+    pub rank: u8,
+}
+
+// 검색어 하나에 대한 이름 일치 검사기 (search_mft, search_in_zip 공용)
+pub struct NameMatcher {
+    mode: SearchMode,
+    regex: Option<Regex>,
+    case_sensitive: bool,
+    // 비교용 검색어 (대소문자를 구분하지 않으면 소문자로 바꾼 값)
+    query_key: String,
+    // Substring 검색어가 여러 단어일 때 모두 포함되어야 하는 단어 목록 (한 단어면 비어 있음)
+    terms: Vec<String>,
+    query_chars: Vec<char>,
+    max_distance: usize,
+}
+
+impl NameMatcher {
+    // 정규식은 병렬 필터 전에 한 번만 컴파일
+    pub fn new(query: &str, mode: SearchMode, case_sensitive: bool) -> Result<Self, String> {
+        let mut query_key = if case_sensitive {
+            query.to_string()
+        } else {
+            query.to_lowercase()
+        };
+        let mut terms = if mode == SearchMode::Substring {
+            split_terms(&query_key)
+        } else {
+            Vec::new()
+        };
+        // 한 단어(또는 따옴표로 묶은 구절 하나)면 기존 부분 문자열 검색과 같음
+        if terms.len() == 1 {
+            query_key = terms.remove(0);
+        }
+        let query_chars: Vec<char> = query_key.chars().collect();
+        Ok(Self {
+            mode,
+            regex: mode.compile(query, case_sensitive)?,
+            case_sensitive,
+            max_distance: fuzzy_max_distance(query_chars.len()),
+            query_key,
+            query_chars,
+            terms,
+        })
+    }
+
+    pub fn matches(&self, name: &str) -> Option<NameMatch> {
+        // 대소문자를 구분하거나 정규식이면 소문자 이름이 필요 없으므로 만들지 않음
+        if self.case_sensitive || self.regex.is_some() {
+            return self.matches_folded(name, name);
+        }
+        self.matches_folded(name, &name.to_lowercase())
+    }
+
+    // 소문자로 바꾼 이름을 미리 가지고 있을 때 (검색 인덱스의 SearchName)
+    pub fn matches_folded(&self, name: &str, folded: &str) -> Option<NameMatch> {
+        let exact = |rank| NameMatch { score: 100, rank };
+        if let Some(re) = &self.regex {
+            return re.is_match(name).then_some(exact(1));
+        }
+        let name_key = if self.case_sensitive { name } else { folded };
+        if !self.terms.is_empty() {
+            if !self
+                .terms
+                .iter()
+                .all(|term| name_key.contains(term.as_str()))
+            {
+                return None;
+            }
+            // 여러 단어 검색은 첫 단어로 시작하는 이름을 먼저 보여줌
+            let rank = if name_key.starts_with(&self.terms[0]) {
+                2
+            } else {
+                1
+            };
+            return Some(exact(rank));
+        }
+        if name_key.contains(&self.query_key) {
+            let stem = name_key.rsplit_once('.').map_or(name_key, |(stem, _)| stem);
+            let rank = if name_key == self.query_key || stem == self.query_key {
+                3
+            } else if name_key.starts_with(&self.query_key) {
+                2
+            } else {
+                1
+            };
+            return Some(exact(rank));
+        }
+        if self.mode != SearchMode::Fuzzy {
+            return None;
+        }
+        let distance = fuzzy_distance(&self.query_chars, name_key, self.max_distance)?;
+        Some(NameMatch {
+            score: fuzzy_score(distance, self.query_chars.len()),
+            rank: 0,
+        })
+    }
 }
 
+// 검색어를 공백으로 나눈 단어 목록 ("..."로 묶은 부분은 공백을 포함한 하나의 구절)
+// 닫는 따옴표가 없으면 끝까지를 구절로 보며, 빈 단어는 버립니다.
+fn split_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut rest = query;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let (term, next) = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.split_once('"').unwrap_or((quoted, ""))
+        } else {
+            rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+        };
+        if !term.is_empty() {
+            terms.push(term.to_string());
+        }
+        rest = next;
+    }
+    terms
+}
+
+// glob 패턴(*, ?)을 파일 이름 전체와 일치하는 정규식으로 변환
+fn glob_to_regex(pattern: &str) -> String {
+    let mut re = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    re
+}
+
+// 폴더 탐색으로 찾은 파일/폴더의 인덱스 항목
+fn walk_entry(entry: &walkdir::DirEntry, parent_frn: u64) -> FileEntry {
+    let is_dir = entry.file_type().is_dir();
+    let metadata = entry.metadata().ok();
+    FileEntry {
+        parent_frn,
+        name: entry.file_name().to_string_lossy().into_owned(),
+        is_dir,
+        size: metadata.as_ref().filter(|_| !is_dir).map(|m| m.len()),
+        modified: metadata
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64),
+    }
+}
+
+// 파일의 FRN (NTFS에서는 USN 레코드의 FileReferenceNumber와 같은 값)
+fn file_reference_number(path: &Path) -> Option<u64> {
+    let mut path_wide: Vec<u16> = path.as_os_str().to_string_lossy().encode_utf16().collect();
+    path_wide.push(0);
+    unsafe {
+        // 속성 조회만 하므로 접근 권한 없이 열고, 폴더도 열 수 있도록 BACKUP_SEMANTICS 사용
+        let handle = CreateFileW(
+            PCWSTR(path_wide.as_ptr()),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAG_BACKUP_SEMANTICS,
+            HANDLE(0),
+        )
+        .ok()?;
+        let mut info = BY_HANDLE_FILE_INFORMATION::default();
+        let result = GetFileInformationByHandle(handle, &mut info);
+        let _ = CloseHandle(handle);
+        result.ok()?;
+        Some(((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64)
+    }
+}
+
+// 인덱스 검증 결과 (validate_index 명령어용)
+#[derive(serde::Serialize)]
+pub struct ValidationReport {
+    // 디스크와 비교한 항목 수
+    pub checked: usize,
+    // 확인한 항목 중 디스크에 없는 항목 수 (상위 폴더 정보가 끊긴 항목 포함)
+    pub stale: usize,
+    // repair로 인덱스에서 지운 항목 수 (사라진 폴더의 하위 항목 포함)
+    pub removed: usize,
+    // rescan으로 인덱스에 새로 추가한 항목 수
+    pub added: usize,
+    // 디스크에 없는 경로 예시 (최대 STALE_PATHS_MAX개)
+    #[serde(rename = "stalePaths")]
+    pub stale_paths: Vec<String>,
+}
+
+const STALE_PATHS_MAX: usize = 100;
+
+// 인덱스에 기록된 폴더의 바로 아래 항목 (list_directory_from_index 명령어용)
+#[derive(serde::Serialize)]
+pub struct DirChild {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "isDir")]
+    pub is_dir: bool,
+    // 인덱스에 캐시된 값만 채움 (아직 조회한 적이 없으면 None)
+    pub size: Option<u64>,
+    pub modified: Option<i64>,
+}
+
+// 경로 비교용 키 (구분자를 '\\'로 통일하고 끝 구분자 제거, 소문자)
+fn path_key(path: &str) -> String {
+    path.replace('/', "\\")
+        .trim_end_matches('\\')
+        .to_lowercase()
+}
+
+// NTFS 루트 디렉터리의 레코드 번호 (FRN의 하위 48비트, 상위 16비트는 시퀀스 번호)
+const ROOT_FRN: u64 = 5;
+const FRN_INDEX_MASK: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+fn is_root_frn(frn: u64) -> bool {
+    (frn & FRN_INDEX_MASK) == ROOT_FRN
+}
+
+// 드라이브 문자("C:", "C:\", "C:/")를 항상 "C:\" 형태의 루트 경로로 정규화
+// (PathBuf::push로 구분자를 붙이면 플랫폼에 따라 결과가 달라지므로 문자열로 직접 만듦)
+fn drive_root(drive_letter: &str) -> PathBuf {
+    PathBuf::from(format!("{}\\", drive_letter.trim_end_matches(['\\', '/'])))
+}
+
+// reconstruct_path가 모은 이름들을 base(드라이브 루트 "C:\" 또는 캐시된 폴더 경로) 아래에 "\"로 이어 붙입니다.
+// parts는 (FRN, 이름, 폴더 여부)를 가장 안쪽 항목부터 모은 것이며, 폴더마다 on_dir(FRN, 그 폴더까지의 경로)를
+// 호출합니다 (경로 캐시 채우기용). drive_root처럼 문자열로 직접 만들므로 플랫폼과 관계없이 결과가 같습니다.
+fn join_parts(
+    base: &str,
+    parts: Vec<(u64, String, bool)>,
+    mut on_dir: impl FnMut(u64, &str),
+) -> String {
+    let mut path = base.to_string();
+    for (frn, name, is_dir) in parts.into_iter().rev() {
+        if !path.ends_with('\\') {
+            path.push('\\');
+        }
+        path.push_str(&name);
+        if is_dir {
+            on_dir(frn, &path);
+        }
+    }
+    path
+}
+
+// 변경 이벤트 묶음 전송 간격과 한 번에 보낼 최대 개수
+// (git checkout, 설치 프로그램처럼 변경이 몰릴 때 프론트엔드에 이벤트가 쏟아지는 것을 방지)
+const CHANGE_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+const CHANGE_BATCH_MAX: usize = 2000;
+
+// 인덱싱 진행 상황(지금까지 처리한 항목 수)을 알리는 최소 간격
+const BUILD_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+// 인덱싱 진행 상황 콜백을 BUILD_PROGRESS_INTERVAL 간격으로 제한
+struct BuildProgress<'a> {
+    on_progress: Option<&'a mut dyn FnMut(usize)>,
+    last: std::time::Instant,
+}
+
+impl<'a> BuildProgress<'a> {
+    fn new(on_progress: Option<&'a mut dyn FnMut(usize)>) -> Self {
+        Self {
+            on_progress,
+            last: std::time::Instant::now(),
+        }
+    }
+
+    fn tick(&mut self, processed: usize) {
+        if self.last.elapsed() >= BUILD_PROGRESS_INTERVAL {
+            self.last = std::time::Instant::now();
+            self.finish(processed);
+        }
+    }
+
+    fn finish(&mut self, processed: usize) {
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            on_progress(processed);
+        }
+    }
+}
+
+// 인덱스 통계 (get_index_stats 명령어용)
+#[derive(serde::Serialize)]
+pub struct IndexStats {
+    #[serde(rename = "entryCount")]
+    pub entry_count: usize,
+    // 항목, 검색용 이름 목록, 폴더 경로 캐시의 대략적인 메모리 사용량
+    #[serde(rename = "memoryBytes")]
+    pub memory_bytes: u64,
+    // 마지막으로 인덱스 파일에 저장(또는 파일에서 로드)된 USN 재시작 지점과 저널 ID
+    #[serde(rename = "nextUsn")]
+    pub next_usn: i64,
+    #[serde(rename = "journalId")]
+    pub journal_id: u64,
+    #[serde(rename = "walkBased")]
+    pub walk_based: bool,
+    // 경로 재구성 깊이 제한에 걸려 결과에서 빠진 횟수 (0이 아니면 max_path_depth를 늘려야 함)
+    #[serde(rename = "truncatedPaths")]
+    pub truncated_paths: usize,
+}
+
+// action은 인덱스에 반영한 결과("create" | "delete" | "rename")이고, reason은 USN 레코드의 원래 사유 비트입니다.
+// 이름 변경(이동 포함)은 RENAME_OLD_NAME과 RENAME_NEW_NAME 두 레코드로 오지만 하나의 rename으로 묶어
+// path에 새 경로, old_path에 이전 경로를 담습니다. reason은 두 레코드의 사유를 합친 값입니다.
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct FileChange {
     pub action: String,
     pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
     pub is_dir: bool,
+    pub reason: u32,
+    // reason을 풀어 쓴 이름 목록 (예: ["rename_new_name", "close"])
+    pub reasons: Vec<&'static str>,
+}
+
+// USN 사유 비트와 프론트엔드에 보낼 이름
+const USN_REASON_NAMES: [(u32, &str); 24] = [
+    (USN_REASON_DATA_OVERWRITE, "data_overwrite"),
+    (USN_REASON_DATA_EXTEND, "data_extend"),
+    (USN_REASON_DATA_TRUNCATION, "data_truncation"),
+    (USN_REASON_NAMED_DATA_OVERWRITE, "named_data_overwrite"),
+    (USN_REASON_NAMED_DATA_EXTEND, "named_data_extend"),
+    (USN_REASON_NAMED_DATA_TRUNCATION, "named_data_truncation"),
+    (USN_REASON_FILE_CREATE, "file_create"),
+    (USN_REASON_FILE_DELETE, "file_delete"),
+    (USN_REASON_EA_CHANGE, "ea_change"),
+    (USN_REASON_SECURITY_CHANGE, "security_change"),
+    (USN_REASON_RENAME_OLD_NAME, "rename_old_name"),
+    (USN_REASON_RENAME_NEW_NAME, "rename_new_name"),
+    (USN_REASON_INDEXABLE_CHANGE, "indexable_change"),
+    (USN_REASON_BASIC_INFO_CHANGE, "basic_info_change"),
+    (USN_REASON_HARD_LINK_CHANGE, "hard_link_change"),
+    (USN_REASON_COMPRESSION_CHANGE, "compression_change"),
+    (USN_REASON_ENCRYPTION_CHANGE, "encryption_change"),
+    (USN_REASON_OBJECT_ID_CHANGE, "object_id_change"),
+    (USN_REASON_REPARSE_POINT_CHANGE, "reparse_point_change"),
+    (USN_REASON_STREAM_CHANGE, "stream_change"),
+    (USN_REASON_TRANSACTED_CHANGE, "transacted_change"),
+    (USN_REASON_INTEGRITY_CHANGE, "integrity_change"),
+    (USN_REASON_CLOSE, "close"),
+    (
+        USN_REASON_DESIRED_STORAGE_CLASS_CHANGE,
+        "desired_storage_class_change",
+    ),
+];
+
+// monitor의 기본 USN 사유 필터 (모니터가 실제로 처리하는 생성/삭제/이름 변경과 내용 변경만)
+// 속성, 보안, 닫기 등 처리하지 않는 사유만 있는 레코드는 저널에서 읽지 않으므로 바쁜 시스템에서
+// CPU와 버퍼 사용이 줄어듭니다. 대신 그런 변경은 file-changes 이벤트로도 오지 않으므로,
+// 속성 변경 같은 더 자세한 변경 추적이 필요하면 0xFFFFFFFF(모든 사유)를 넘기면 됩니다.
+pub const DEFAULT_REASON_MASK: u32 = USN_REASON_FILE_CREATE
+    | USN_REASON_FILE_DELETE
+    | USN_REASON_RENAME_OLD_NAME
+    | USN_REASON_RENAME_NEW_NAME
+    | USN_REASON_DATA_OVERWRITE
+    | USN_REASON_DATA_EXTEND
+    | USN_REASON_DATA_TRUNCATION;
+
+fn reason_names(reason: u32) -> Vec<&'static str> {
+    USN_REASON_NAMES
+        .iter()
+        .filter(|(bit, _)| reason & bit != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+// 검색용 이름 목록의 항목
+// 대소문자 구분 없는 검색에서 검색할 때마다 이름마다 소문자 문자열을 만들지 않도록 미리 소문자로 바꿔 둡니다.
+// 소문자 이름은 원래 이름과 다를 때만 저장하므로, 추가 메모리는 대문자가 섞인 이름마다
+// 약 16바이트 + 이름 길이입니다 (평균 이름 20바이트 기준, 100만 개에 약 30-40MB).
+// 대신 검색 한 번에 항목 수만큼 일어나던 문자열 할당이 없어집니다.
+struct SearchName {
+    frn: u64,
+    name: String,
+    folded: Option<Box<str>>,
+}
+
+impl SearchName {
+    fn new(frn: u64, name: String) -> Self {
+        let lower = name.to_lowercase();
+        let folded = (lower != name).then(|| lower.into_boxed_str());
+        Self { frn, name, folded }
+    }
+
+    // 소문자로 바꾼 이름
+    fn folded(&self) -> &str {
+        self.folded.as_deref().unwrap_or(&self.name)
+    }
+
+    fn memory_bytes(&self) -> usize {
+        self.name.capacity() + self.folded.as_ref().map_or(0, |f| f.len())
+    }
+}
+
+// 경로 재구성 시 따라 올라갈 최대 폴더 깊이 기본값 (순환 참조로 인한 무한 루프 방지용)
+pub const DEFAULT_MAX_PATH_DEPTH: usize = 50;
+
+// 인덱스 상태 (get_index_state 명령어와 "index-state" 이벤트용)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexState {
+    // 인덱스 파일이 없거나 불러오지 못함
+    NotBuilt,
+    // 인덱싱 중이거나 인덱스 파일을 불러오는 중
+    Building,
+    // 검색 가능 (USN 저널 인덱스는 모니터링 중)
+    Ready,
+    // 저널을 이어서 읽을 수 없거나 파일 형식이 바뀌어 다시 인덱싱해야 함 (기존 결과로 검색은 가능)
+    Stale,
 }
 
 // 전역 인덱스 저장소 (FRN -> FileEntry)
 pub struct MftIndex {
     pub entries: DashMap<u64, FileEntry>,
-    search_index: RwLock<Vec<(u64, String)>>,
+    search_index: RwLock<Vec<SearchName>>,
+    // 폴더 FRN -> 전체 경로 캐시 (같은 상위 폴더를 공유하는 파일의 경로 재구성 반복 방지)
+    // 폴더가 삭제/이름 변경되면 하위 경로가 모두 바뀌므로 통째로 비웁니다.
+    dir_paths: DashMap<u64, PathBuf>,
     pub drive_letter: String,
+    // 실행 중인 모니터의 세대 번호 (값이 바뀌면 이전 모니터는 종료)
+    monitor_generation: AtomicU64,
+    // MFT를 읽을 수 없어 폴더 탐색으로 만든 인덱스인지 (실시간 모니터링 없음)
+    walk_based: AtomicBool,
+    // 모니터가 인덱스에 반영한 마지막 USN (주기적 저장 시 재시작 지점으로 사용)
+    current_usn: AtomicI64,
+    // 인덱스 파일에 기록된 next_usn / journal_id (통계 표시용)
+    saved_usn: AtomicI64,
+    journal_id: AtomicU64,
+    state: Mutex<IndexState>,
+    // 경로 재구성 최대 깊이와 그 제한에 걸린 횟수
+    max_path_depth: AtomicUsize,
+    truncated_paths: AtomicUsize,
 }
 
 impl MftIndex {
@@ -54,10 +786,79 @@ impl MftIndex {
         Self {
             entries: DashMap::new(),
             search_index: RwLock::new(Vec::new()),
+            dir_paths: DashMap::new(),
             drive_letter,
+            monitor_generation: AtomicU64::new(0),
+            walk_based: AtomicBool::new(false),
+            current_usn: AtomicI64::new(0),
+            saved_usn: AtomicI64::new(0),
+            journal_id: AtomicU64::new(0),
+            state: Mutex::new(IndexState::NotBuilt),
+            max_path_depth: AtomicUsize::new(DEFAULT_MAX_PATH_DEPTH),
+            truncated_paths: AtomicUsize::new(0),
+        }
+    }
+
+    // 경로 재구성 최대 깊이 변경 (이전에 제한에 걸린 횟수는 초기화)
+    pub fn set_max_path_depth(&self, depth: usize) {
+        self.max_path_depth.store(depth, Ordering::SeqCst);
+        self.truncated_paths.store(0, Ordering::SeqCst);
+    }
+
+    pub fn state(&self) -> IndexState {
+        *self.state.lock().unwrap()
+    }
+
+    pub fn set_state(&self, state: IndexState) {
+        *self.state.lock().unwrap() = state;
+    }
+
+    // 인덱스에 반영된 마지막 USN
+    pub fn current_usn(&self) -> i64 {
+        self.current_usn.load(Ordering::SeqCst)
+    }
+
+    // 폴더 탐색 기반 인덱스이면 USN 저널 모니터링을 시작하면 안 됩니다.
+    pub fn is_walk_based(&self) -> bool {
+        self.walk_based.load(Ordering::SeqCst)
+    }
+
+    // 인덱스 통계 (메모리 사용량은 항목을 한 번 순회해 계산하는 추정치)
+    pub fn stats(&self) -> IndexStats {
+        let entries: usize = self
+            .entries
+            .iter()
+            .map(|r| size_of::<(u64, FileEntry)>() + r.value().name.capacity())
+            .sum();
+        let search_index = self
+            .search_index
+            .read()
+            .map(|list| {
+                list.capacity() * size_of::<SearchName>()
+                    + list.iter().map(SearchName::memory_bytes).sum::<usize>()
+            })
+            .unwrap_or(0);
+        let dir_paths: usize = self
+            .dir_paths
+            .iter()
+            .map(|r| size_of::<(u64, PathBuf)>() + r.value().capacity())
+            .sum();
+
+        IndexStats {
+            entry_count: self.entries.len(),
+            memory_bytes: (entries + search_index + dir_paths) as u64,
+            next_usn: self.saved_usn.load(Ordering::SeqCst),
+            journal_id: self.journal_id.load(Ordering::SeqCst),
+            walk_based: self.is_walk_based(),
+            truncated_paths: self.truncated_paths.load(Ordering::Relaxed),
         }
     }
 
+    // 실행 중인 모니터를 종료합니다 (다음 루프 반복에서 볼륨 핸들을 닫고 빠져나옴).
+    pub fn stop_monitoring(&self) {
+        self.monitor_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
     // 1. 볼륨 핸들 획득
     fn get_volume_handle(&self) -> Result<HANDLE, String> {
         let drive = &self.drive_letter;
@@ -85,59 +886,141 @@ impl MftIndex {
         }
     }
 
+    // 현재 USN 저널 상태 조회
+    fn query_journal(handle: HANDLE) -> windows::core::Result<USN_JOURNAL_DATA_V0> {
+        let mut journal_data = USN_JOURNAL_DATA_V0::default();
+        let mut bytes_returned = 0u32;
+        unsafe {
+            DeviceIoControl(
+                handle,
+                FSCTL_QUERY_USN_JOURNAL,
+                None,
+                0,
+                Some(&mut journal_data as *mut _ as *mut _),
+                size_of::<USN_JOURNAL_DATA_V0>() as u32,
+                Some(&mut bytes_returned),
+                None,
+            )?;
+        }
+        Ok(journal_data)
+    }
+
     // 인덱스를 파일에 저장
     pub fn save_to_disk(&self, path: &Path, next_usn: i64, journal_id: u64) -> Result<(), String> {
         let persistent_data = PersistentData {
             entries: self.entries.iter().map(|r| (*r.key(), r.value().clone())).collect(),
             next_usn,
             journal_id,
+            walk_based: self.is_walk_based(),
         };
 
-        let file = File::create(path).map_err(|e| format!("Failed to create index file: {}", e))?;
-        let writer = BufWriter::new(file);
+        let header = IndexHeader {
+            magic: INDEX_MAGIC,
+            version: INDEX_VERSION,
+        };
+
+        // 저장 도중 종료되어도 기존 인덱스가 깨지지 않도록 임시 파일에 쓴 뒤 교체
+        let temp_path = path.with_extension("bin.tmp");
+        let file =
+            File::create(&temp_path).map_err(|e| format!("Failed to create index file: {}", e))?;
+        let mut writer = BufWriter::new(file);
         // bincode는 빠르고 간결한 직렬화/역직렬화 라이브러리입니다.
-        bincode::serialize_into(writer, &persistent_data)
+        bincode::serialize_into(&mut writer, &header)
+            .map_err(|e| format!("Failed to serialize index: {}", e))?;
+
+        let mut encoder = zstd::stream::Encoder::new(writer, INDEX_ZSTD_LEVEL)
+            .map_err(|e| format!("Failed to compress index: {}", e))?;
+        bincode::serialize_into(&mut encoder, &persistent_data)
             .map_err(|e| format!("Failed to serialize index: {}", e))?;
+        encoder
+            .finish()
+            .and_then(|mut writer| writer.flush())
+            .map_err(|e| format!("Failed to compress index: {}", e))?;
+
+        std::fs::rename(&temp_path, path).map_err(|e| format!("Failed to save index file: {}", e))?;
+        self.saved_usn.store(next_usn, Ordering::SeqCst);
+        self.journal_id.store(journal_id, Ordering::SeqCst);
         Ok(())
     }
 
     // 파일에서 인덱스를 로드
+    // 헤더가 없는 초기 형식은 변환해서 읽고, 알 수 없는 버전이거나 내용이 깨진 경우
+    // INDEX_REBUILD_REQUIRED를 반환합니다.
     pub fn load_from_disk(&self, path: &Path) -> Result<(i64, u64), String> {
         let file = File::open(path).map_err(|e| format!("Failed to open index file: {}", e))?;
-        let reader = BufReader::new(file);
-        let persistent_data: PersistentData = bincode::deserialize_from(reader)
-            .map_err(|e| format!("Failed to deserialize index: {}", e))?;
+        let mut reader = BufReader::new(file);
+
+        let header: Option<IndexHeader> = bincode::deserialize_from(&mut reader)
+            .ok()
+            .filter(|h: &IndexHeader| h.magic == INDEX_MAGIC);
+        let persistent_data: PersistentData = match header {
+            Some(h) if h.version == INDEX_VERSION => {
+                let decoder = zstd::stream::Decoder::with_buffer(reader)
+                    .map_err(|_| INDEX_REBUILD_REQUIRED.to_string())?;
+                bincode::deserialize_from(decoder)
+                    .map_err(|_| INDEX_REBUILD_REQUIRED.to_string())?
+            }
+            Some(h) if h.version == 2 => bincode::deserialize_from(reader)
+                .map_err(|_| INDEX_REBUILD_REQUIRED.to_string())?,
+            Some(h) if h.version == 1 => {
+                let v1: PersistentDataV1 = bincode::deserialize_from(reader)
+                    .map_err(|_| INDEX_REBUILD_REQUIRED.to_string())?;
+                v1.into()
+            }
+            Some(_) => return Err(INDEX_REBUILD_REQUIRED.to_string()),
+            None => {
+                // 헤더가 없으면 처음부터 초기 형식으로 다시 읽음
+                let file =
+                    File::open(path).map_err(|e| format!("Failed to open index file: {}", e))?;
+                let legacy: LegacyPersistentData =
+                    bincode::deserialize_from(BufReader::new(file))
+                        .map_err(|_| INDEX_REBUILD_REQUIRED.to_string())?;
+                legacy.into()
+            }
+        };
 
         self.entries.clear();
         for (k, v) in persistent_data.entries {
             self.entries.insert(k, v);
         }
 
+        self.walk_based
+            .store(persistent_data.walk_based, Ordering::SeqCst);
         self.rebuild_search_index()?;
-        
+        self.saved_usn.store(persistent_data.next_usn, Ordering::SeqCst);
+        self.journal_id.store(persistent_data.journal_id, Ordering::SeqCst);
+
         Ok((persistent_data.next_usn, persistent_data.journal_id))
     }
 
     // 2. MFT 인덱싱 (FSCTL_ENUM_USN_DATA)
-    pub fn build_index(&self) -> Result<(usize, i64, u64), String> {
-        let handle = self.get_volume_handle()?;
+    // 관리자 권한이 없거나 NTFS가 아니어서 볼륨/USN 저널을 열 수 없으면 폴더 탐색으로 대신 만듭니다.
+    // on_progress를 지정하면 인덱싱하는 동안 BUILD_PROGRESS_INTERVAL마다 지금까지 처리한 항목 수로 호출하고
+    // (전체 개수는 미리 알 수 없음), 검색 인덱스를 만들기 전에 최종 개수로 한 번 더 호출합니다.
+    pub fn build_index(
+        &self,
+        on_progress: Option<&mut dyn FnMut(usize)>,
+    ) -> Result<(usize, i64, u64), String> {
+        let mut progress = BuildProgress::new(on_progress);
+        let handle = match self.get_volume_handle() {
+            Ok(handle) => handle,
+            Err(e) => {
+                println!("{} Falling back to directory walking.", e);
+                return self.build_index_walk(&mut progress);
+            }
+        };
         self.entries.clear();
 
         // 1. 현재 USN 저널 상태 조회 (모니터링 시작점 확보)
-        let mut journal_data = USN_JOURNAL_DATA_V0::default();
-        let mut bytes_returned = 0u32;
-        unsafe {
-            DeviceIoControl(
-                handle,
-                FSCTL_QUERY_USN_JOURNAL,
-                None,
-                0,
-                Some(&mut journal_data as *mut _ as *mut _),
-                size_of::<USN_JOURNAL_DATA_V0>() as u32,
-                Some(&mut bytes_returned),
-                None,
-            ).map_err(|e| format!("Failed to query USN journal: {}", e))?;
-        }
+        let journal_data = match Self::query_journal(handle) {
+            Ok(data) => data,
+            Err(e) => {
+                let _ = unsafe { CloseHandle(handle) };
+                println!("Failed to query USN journal: {}. Falling back to directory walking.", e);
+                return self.build_index_walk(&mut progress);
+            }
+        };
+        self.walk_based.store(false, Ordering::SeqCst);
 
         // MFT 열거 설정
         let mut mft_enum_data = MFT_ENUM_DATA_V0 {
@@ -216,30 +1099,98 @@ impl MftIndex {
                             parent_frn,
                             name,
                             is_dir,
+                            size: None,
+                            modified: filetime_to_millis(record.TimeStamp),
                         },
                     );
                 }
 
                 offset += record_len;
             }
+            progress.tick(self.entries.len());
         }
 
         let _ = unsafe { CloseHandle(handle) };
+        progress.finish(self.entries.len());
 
         self.rebuild_search_index()?;
 
         Ok((self.entries.len(), journal_data.NextUsn, journal_data.UsnJournalID))
     }
 
+    // 2-1. 폴더 탐색 인덱싱 (MFT를 읽을 수 없는 경우의 대체 방식)
+    // FRN 대신 임의의 번호를 붙이고 최상위 항목의 부모를 루트(Index 5)로 두어 경로 재구성을 그대로 사용합니다.
+    // USN 저널이 없으므로 next_usn/journal_id는 0이며, 변경 사항은 다시 인덱싱해야 반영됩니다.
+    fn build_index_walk(&self, progress: &mut BuildProgress) -> Result<(usize, i64, u64), String> {
+        self.entries.clear();
+        self.walk_based.store(true, Ordering::SeqCst);
+
+        let root = drive_root(&self.drive_letter);
+
+        let mut dir_ids: HashMap<PathBuf, u64> = HashMap::new();
+        let mut next_id = 16u64;
+        // 권한이 없는 폴더 등은 건너뜀
+        for entry in walkdir::WalkDir::new(&root).min_depth(1).into_iter().flatten() {
+            let parent_frn = entry
+                .path()
+                .parent()
+                .and_then(|p| dir_ids.get(p).copied())
+                .unwrap_or(ROOT_FRN);
+            let frn = next_id;
+            next_id += 1;
+            if entry.file_type().is_dir() {
+                dir_ids.insert(entry.path().to_path_buf(), frn);
+            }
+            self.entries.insert(frn, walk_entry(&entry, parent_frn));
+            progress.tick(self.entries.len());
+        }
+        progress.finish(self.entries.len());
+
+        self.rebuild_search_index()?;
+
+        Ok((self.entries.len(), 0, 0))
+    }
+
     // 4. USN 저널 모니터링 (실시간 업데이트)
-    pub fn monitor<F>(&self, start_usn: i64, journal_id: u64, on_change: F)
-    where
+    // 새 모니터를 시작하면 같은 인덱스의 이전 모니터는 자동으로 종료됩니다.
+    // 변경 내역은 모아서 최대 200ms마다(또는 2000개가 쌓이면 즉시) on_change로 전달합니다.
+    // 저널이 삭제/재생성되었거나 시작 USN이 이미 저널에서 밀려난 경우 on_stale을 호출하고 종료합니다.
+    // 이때는 변경 내역을 이어서 받을 수 없으므로 인덱스를 다시 만들어야 합니다.
+    // reason_mask에 해당하는 사유가 하나라도 있는 레코드만 읽습니다 (DEFAULT_REASON_MASK 참고).
+    pub fn monitor<F, S>(
+        &self,
+        start_usn: i64,
+        journal_id: u64,
+        reason_mask: u32,
+        on_change: F,
+        on_stale: S,
+    ) where
         F: Fn(Vec<FileChange>) + Send + Sync + 'static,
+        S: FnOnce(String),
     {
+        let generation = self.monitor_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.current_usn.store(start_usn, Ordering::SeqCst);
+
         if let Ok(handle) = self.get_volume_handle() {
+            let stale_reason = match Self::query_journal(handle) {
+                Err(e) => Some(format!("USN journal is not available: {}", e)),
+                Ok(journal) if journal.UsnJournalID != journal_id => {
+                    Some("USN journal was recreated".to_string())
+                }
+                Ok(journal) if start_usn < journal.FirstUsn => {
+                    Some("USN journal wrapped past the saved position".to_string())
+                }
+                Ok(_) => None,
+            };
+            if let Some(reason) = stale_reason {
+                let _ = unsafe { CloseHandle(handle) };
+                on_stale(reason);
+                return;
+            }
+
             let mut read_data = READ_USN_JOURNAL_DATA_V0 {
                 StartUsn: start_usn,
-                ReasonMask: 0xFFFFFFFF,
+                ReasonMask: reason_mask,
                 ReturnOnlyOnClose: 0,
                 Timeout: 1, // 0 is busy-wait, use a timeout
                 BytesToWaitFor: 0,
@@ -248,8 +1199,17 @@ impl MftIndex {
 
             let mut buffer = vec![0u8; 64 * 1024];
             let mut bytes_returned = 0u32;
+            let mut pending: Vec<FileChange> = Vec::new();
+            // 새 이름 레코드를 기다리는 이름 변경 (FRN -> 이전 경로의 delete, 다음 묶음에 올 수도 있음)
+            let mut renames: HashMap<u64, FileChange> = HashMap::new();
+            let mut last_flush = std::time::Instant::now();
 
             loop {
+                // 정지 요청(또는 새 모니터 시작) 확인
+                if self.monitor_generation.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+
                 let result = unsafe {
                     DeviceIoControl(
                         handle,
@@ -295,134 +1255,710 @@ impl MftIndex {
 
                             // Handle different reasons
                             if (record.Reason & (USN_REASON_FILE_DELETE | USN_REASON_RENAME_OLD_NAME)) != 0 {
+                                let mut removed_dir = false;
                                 if let Some(entry) = self.entries.get(&frn) {
+                                    removed_dir = entry.is_dir;
                                     if let Some(parent_path) = self.reconstruct_path(&entry.parent_frn) {
                                         let full_path = parent_path.join(&entry.name);
-                                        changes.push(FileChange {
+                                        let change = FileChange {
                                             action: "delete".to_string(),
                                             path: full_path.to_string_lossy().to_string(),
+                                            old_path: None,
                                             is_dir: entry.is_dir,
-                                        });
+                                            reason: record.Reason,
+                                            reasons: reason_names(record.Reason),
+                                        };
+                                        if (record.Reason & USN_REASON_FILE_DELETE) == 0 {
+                                            renames.insert(frn, change);
+                                        } else {
+                                            changes.push(change);
+                                        }
                                     }
                                 }
                                 self.entries.remove(&frn);
+                                if removed_dir {
+                                    self.dir_paths.clear();
+                                }
                                 if let Ok(mut search_idx) = self.search_index.write() {
-                                    search_idx.retain(|(entry_frn, _)| *entry_frn != frn);
+                                    search_idx.retain(|entry| entry.frn != frn);
                                 }
                             } else if (record.Reason & (USN_REASON_FILE_CREATE | USN_REASON_RENAME_NEW_NAME)) != 0 {
                                 let parent_frn = record.ParentFileReferenceNumber;
                                 let is_dir = (record.FileAttributes & FILE_ATTRIBUTE_DIRECTORY.0) != 0;
-                                self.entries.insert(frn, FileEntry { parent_frn, name: name.clone(), is_dir });
+                                self.entries.insert(frn, FileEntry {
+                                    parent_frn,
+                                    name: name.clone(),
+                                    is_dir,
+                                    size: None,
+                                    modified: filetime_to_millis(record.TimeStamp),
+                                });
                                 if let Ok(mut search_idx) = self.search_index.write() {
-                                    search_idx.retain(|(entry_frn, _)| *entry_frn != frn); // Remove old entry if it was a rename
-                                    search_idx.push((frn, name.clone()));
+                                    search_idx.retain(|entry| entry.frn != frn); // Remove old entry if it was a rename
+                                    search_idx.push(SearchName::new(frn, name.clone()));
                                 }
                                 
                                 if let Some(parent_path) = self.reconstruct_path(&parent_frn) {
                                     let full_path = parent_path.join(&name);
-                                    changes.push(FileChange {
-                                        action: "create".to_string(),
-                                        path: full_path.to_string_lossy().to_string(),
-                                        is_dir,
+                                    let path = full_path.to_string_lossy().to_string();
+                                    changes.push(match renames.remove(&frn) {
+                                        Some(old) => {
+                                            let reason = old.reason | record.Reason;
+                                            FileChange {
+                                                action: "rename".to_string(),
+                                                path,
+                                                old_path: Some(old.path),
+                                                is_dir,
+                                                reason,
+                                                reasons: reason_names(reason),
+                                            }
+                                        }
+                                        None => FileChange {
+                                            action: "create".to_string(),
+                                            path,
+                                            old_path: None,
+                                            is_dir,
+                                            reason: record.Reason,
+                                            reasons: reason_names(record.Reason),
+                                        },
                                     });
                                 }
+                            } else if (record.Reason & (USN_REASON_DATA_OVERWRITE | USN_REASON_DATA_EXTEND | USN_REASON_DATA_TRUNCATION)) != 0 {
+                                // 내용이 바뀐 파일은 캐시된 크기를 버리고 수정 시각을 갱신
+                                if let Some(mut entry) = self.entries.get_mut(&frn) {
+                                    entry.size = None;
+                                    entry.modified = filetime_to_millis(record.TimeStamp);
+                                }
                             }
                         }
 
                         offset += record_len;
                     }
-                    if !changes.is_empty() {
-                        on_change(changes);
+                    // 이번 묶음까지 인덱스에 반영됨
+                    self.current_usn.store(next_usn, Ordering::SeqCst);
+                    pending.extend(changes);
+                    if !pending.is_empty()
+                        && (pending.len() >= CHANGE_BATCH_MAX
+                            || last_flush.elapsed() >= CHANGE_FLUSH_INTERVAL)
+                    {
+                        on_change(std::mem::take(&mut pending));
+                        last_flush = std::time::Instant::now();
                     }
                 } else {
+                    // 새 변경이 없는데도 짝이 오지 않은 이름 변경은 (볼륨 밖으로 옮겨지는 등) 삭제로 처리
+                    pending.extend(renames.drain().map(|(_, change)| change));
+                    // 새 변경이 없으면 쌓여 있던 내역을 바로 전송
+                    if !pending.is_empty() {
+                        on_change(std::mem::take(&mut pending));
+                        last_flush = std::time::Instant::now();
+                    }
+                    if let Err(err) = &result {
+                        let code = err.code();
+                        if code == ERROR_JOURNAL_ENTRY_DELETED.into()
+                            || code == ERROR_JOURNAL_NOT_ACTIVE.into()
+                            || code == ERROR_JOURNAL_DELETE_IN_PROGRESS.into()
+                        {
+                            let _ = unsafe { CloseHandle(handle) };
+                            on_stale(format!("USN journal is no longer readable: {}", err));
+                            return;
+                        }
+                    }
                     std::thread::sleep(std::time::Duration::from_millis(500));
                 }
             }
+            let _ = unsafe { CloseHandle(handle) };
         }
     }
 
     // 3. 검색 및 경로 재구성
-    pub fn search(&self, query: &str, use_regex: bool) -> Vec<PathBuf> {
+    // limit이 None이면 전체 결과를 반환하며, 잘리기 전의 전체 일치 개수를 함께 반환합니다.
+    // 정렬 기준이 있으면 크기가 큰 순서 또는 최근 수정 순서로 정렬합니다.
+    // 일치 개수는 병렬 필터에서 바로 세고 정렬도 경로 없이 하므로, 전체 경로는 반환할 페이지의 항목만 재구성합니다.
+    // (제외 경로 확인에 필요한 부모 폴더 경로는 폴더마다 한 번만 만들고, 디스크에서 크기/시각을 읽어야 하는 항목은 그때 만든 경로를 재사용)
+    pub fn search(
+        &self,
+        query: &str,
+        mode: SearchMode,
+        filter: &SearchFilter,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<(Vec<SearchHit>, usize), String> {
+        let matcher = NameMatcher::new(query, mode, filter.case_sensitive)?;
+
         let search_idx = self.search_index.read().unwrap();
+        // 확장자는 ".pdf"와 "pdf" 모두 허용
+        let ext_suffix = filter
+            .ext
+            .as_ref()
+            .map(|ext| format!(".{}", ext.trim_start_matches('.').to_lowercase()));
+        let exclusions: Vec<Vec<String>> = filter
+            .exclude_paths
+            .iter()
+            .map(|rule| {
+                rule.split(['/', '\\'])
+                    .filter(|part| !part.is_empty())
+                    .map(|part| part.to_lowercase())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|rule| !rule.is_empty())
+            .collect();
 
-        let regex = if use_regex {
-            RegexBuilder::new(query)
-                .case_insensitive(true)
-                .build()
-                .ok()
-        } else {
-            None
+        // 일치한 항목의 부모 폴더별 이름 목록 (소문자, 제외 경로가 없으면 비워 둠)
+        // 경로를 재구성할 수 없는 폴더(삭제됨, 너무 깊음)는 None이며 그 아래 항목은 결과에서 뺌
+        let parent_parts: DashMap<u64, Option<Arc<Vec<String>>>> = DashMap::new();
+        let parts_of = |parent: u64| -> Option<Arc<Vec<String>>> {
+            if let Some(parts) = parent_parts.get(&parent) {
+                return parts.clone();
+            }
+            let parts = self.reconstruct_path(&parent).map(|path| {
+                Arc::new(if exclusions.is_empty() {
+                    Vec::new()
+                } else {
+                    lowercase_parts(&path)
+                })
+            });
+            parent_parts.insert(parent, parts.clone());
+            parts
         };
-        let query_lower = query.to_lowercase();
+        let total = AtomicUsize::new(0);
 
         // Rayon을 사용한 병렬 검색 (초고속 검색의 핵심)
-        search_idx
+        let mut matches = search_idx
             .par_iter()
-            .filter(|(_, name)| {
-                if let Some(re) = &regex {
-                    re.is_match(name)
-                } else {
-                    name.to_lowercase().contains(&query_lower)
+            .filter_map(|entry| {
+                let m = matcher.matches_folded(&entry.name, entry.folded())?;
+                Some((entry, m))
+            })
+            .filter_map(|(entry, m)| {
+                let (is_dir, parent) = {
+                    let indexed = self.entries.get(&entry.frn)?;
+                    (indexed.is_dir, indexed.parent_frn)
+                };
+                if filter.is_active()
+                    && (is_dir
+                        || !ext_suffix
+                            .as_ref()
+                            .is_none_or(|suffix| entry.folded().ends_with(suffix)))
+                {
+                    return None;
                 }
+                let parts = parts_of(parent)?;
+                if is_excluded(&parts, entry.folded(), &exclusions) {
+                    return None;
+                }
+                let mut path = None;
+                let (size, modified) = if filter.needs_metadata() {
+                    let (size, modified) = self.entry_metadata(entry.frn, &mut path)?;
+                    if !filter.metadata_matches(size, modified) {
+                        return None;
+                    }
+                    (size, modified)
+                } else {
+                    (0, 0)
+                };
+                total.fetch_add(1, Ordering::Relaxed);
+                // 경로는 잘라낸 결과에만 채움 (디스크 조회로 이미 만든 경로는 그대로 사용)
+                // drive도 잘라낸 결과에만 채움 (빈 String은 할당하지 않음)
+                Some(SearchHit {
+                    path: path.unwrap_or_default(),
+                    score: m.score,
+                    drive: String::new(),
+                    rank: m.rank,
+                    size,
+                    modified,
+                    frn: entry.frn,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // 반환할 범위(offset + limit)까지만 골라낸 뒤 그 안에서만 정렬
+        let end = limit.map_or(matches.len(), |limit| offset.saturating_add(limit));
+        if end < matches.len() {
+            matches.select_nth_unstable_by(end, |a, b| filter.compare_hits(a, b));
+            matches.truncate(end);
+        }
+        matches.par_sort_unstable_by(|a, b| filter.compare_hits(a, b));
+
+        let hits: Vec<SearchHit> = matches
+            .into_par_iter()
+            .skip(offset)
+            .filter_map(|hit| {
+                let path = if hit.path.as_os_str().is_empty() {
+                    self.reconstruct_path(&hit.frn)?
+                } else {
+                    hit.path
+                };
+                Some(SearchHit {
+                    path,
+                    drive: self.drive_letter.clone(),
+                    ..hit
+                })
             })
-            .filter_map(|(frn, _)| self.reconstruct_path(frn))
-            .collect::<Vec<_>>() // 일단 병렬로 수집
-            .into_iter() // 일반 Iterator로 변환
-            .take(500)
-            .collect()
+            .collect();
+        Ok((hits, total.into_inner()))
+    }
+
+    // 파일 크기와 수정 시각 조회 (한 번 조회한 값은 엔트리에 캐시)
+    // 캐시에 없으면 경로를 재구성해 디스크에서 읽고, 만든 경로는 path에 남깁니다.
+    fn entry_metadata(&self, frn: u64, path: &mut Option<PathBuf>) -> Option<(u64, i64)> {
+        if let Some(entry) = self.entries.get(&frn) {
+            if let (Some(size), Some(modified)) = (entry.size, entry.modified) {
+                return Some((size, modified));
+            }
+        }
+        let metadata = std::fs::metadata(path.insert(self.reconstruct_path(&frn)?)).ok()?;
+        let size = metadata.len();
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_millis() as i64);
+        if let Some(mut entry) = self.entries.get_mut(&frn) {
+            entry.size = Some(size);
+            entry.modified = Some(modified);
+        }
+        Some((size, modified))
     }
 
     // 부모 FRN을 타고 올라가며 경로 완성
+    // 캐시된 상위 폴더를 만나면 거기서 멈추고, 지나온 폴더들의 경로를 캐시에 채웁니다.
+    // max_path_depth 안에 루트나 캐시된 폴더에 도달하지 못하면 잘린 경로 대신 None을 반환하고
+    // 그 횟수를 통계(truncatedPaths)에 남기며, 처음 한 번은 로그로도 알립니다.
     fn reconstruct_path(&self, frn: &u64) -> Option<PathBuf> {
         let mut path_parts = Vec::new();
         let mut current_frn = *frn;
+        let mut base = None;
+        // 루트나 캐시된 폴더까지 제대로 도달했는지
+        let mut complete = false;
 
         // 무한 루프 방지 (최대 깊이 제한)
-        for _ in 0..50 {
+        let max_depth = self.max_path_depth.load(Ordering::Relaxed);
+        for _ in 0..max_depth {
+            if let Some(cached) = self.dir_paths.get(&current_frn) {
+                base = Some(cached.clone());
+                complete = true;
+                break;
+            }
+
+            // NTFS 루트 디렉터리 (Index 5) 도달
+            // 루트 항목 자신(이름 ".")은 경로에 넣지 않으므로 루트에 바로 있는 파일은 "C:\file"이 됩니다.
+            if is_root_frn(current_frn) {
+                complete = true;
+                break;
+            }
+
             if let Some(entry) = self.entries.get(&current_frn) {
-                path_parts.push(entry.name.clone());
+                path_parts.push((current_frn, entry.name.clone(), entry.is_dir));
                 let parent = entry.parent_frn;
 
                 // 루트 도달 체크 (자신이 부모인 경우 등)
                 // 일반적으로 루트 디렉터리의 부모는 자기 자신이거나 특정 고정값입니다.
                 if parent == current_frn || parent == 0 {
-                    break;
-                }
-
-                // NTFS 루트 디렉터리 (Index 5) 체크
-                if (parent & 0x0000_FFFF_FFFF_FFFF) == 5 {
+                    complete = true;
                     break;
                 }
 
                 current_frn = parent;
             } else {
-                // 부모가 없지만 루트(Index 5)인 경우
-                if (current_frn & 0x0000_FFFF_FFFF_FFFF) == 5 {
+                return None; // 부모 정보 유실 (삭제된 파일 등)
+            }
+        }
+
+        if !complete {
+            if self.truncated_paths.fetch_add(1, Ordering::Relaxed) == 0 {
+                println!(
+                    "{}: path of FRN {} is deeper than {} folders and was skipped (raise max_path_depth)",
+                    self.drive_letter, frn, max_depth
+                );
+            }
+            return None;
+        }
+
+        let base = base.unwrap_or_else(|| drive_root(&self.drive_letter));
+        let path = join_parts(&base.to_string_lossy(), path_parts, |dir_frn, dir_path| {
+            self.dir_paths.insert(dir_frn, PathBuf::from(dir_path));
+        });
+        Some(PathBuf::from(path))
+    }
+
+    // 폴더의 바로 아래 항목을 디스크를 읽지 않고 인덱스만으로 조회합니다.
+    // 마지막 이름이 같은 폴더 항목들의 경로를 reconstruct_path로 만들어 대상 폴더를 찾으며,
+    // 인덱스에 없는 폴더면 None을 반환합니다. 폴더를 먼저, 같은 종류끼리는 이름 순으로 정렬합니다.
+    pub fn list_dir(&self, dir_path: &str) -> Option<Vec<DirChild>> {
+        let key = path_key(dir_path);
+        let (dir_frn, base) = if key == path_key(&self.drive_letter) {
+            (None, drive_root(&self.drive_letter))
+        } else {
+            let name = key.rsplit('\\').next()?;
+            self.entries
+                .par_iter()
+                .filter(|r| r.value().is_dir && r.value().name.to_lowercase() == name)
+                .find_map_any(|r| {
+                    let path = self.reconstruct_path(r.key())?;
+                    (path_key(&path.to_string_lossy()) == key).then_some((Some(*r.key()), path))
+                })?
+        };
+
+        let mut children: Vec<DirChild> = self
+            .entries
+            .par_iter()
+            .filter(|r| match dir_frn {
+                Some(frn) => r.value().parent_frn == frn && *r.key() != frn,
+                // 루트 항목 자신(부모가 자기 자신)은 제외
+                None => is_root_frn(r.value().parent_frn) && !is_root_frn(*r.key()),
+            })
+            .map(|r| {
+                let entry = r.value();
+                DirChild {
+                    name: entry.name.clone(),
+                    path: base.join(&entry.name).to_string_lossy().into_owned(),
+                    is_dir: entry.is_dir,
+                    size: entry.size,
+                    modified: entry.modified,
+                }
+            })
+            .collect();
+        children.sort_by_cached_key(|c| (!c.is_dir, c.name.to_lowercase()));
+        Some(children)
+    }
+
+    // 인덱스를 디스크와 비교해 어긋난 항목을 찾습니다 (모니터가 놓친 변경 복구용).
+    // sample을 지정하면 전체 항목 중 고르게 그 개수만 확인하고, 없으면 전체를 확인합니다.
+    // repair면 디스크에 없는 항목과 그 하위 항목을 인덱스에서 지우고,
+    // rescan이면 확인한 폴더(전체 확인이면 루트 포함)를 다시 읽어 인덱스에 없는 항목을 하위 폴더째 추가합니다.
+    // 권한 문제 등으로 확인할 수 없는 경로는 있는 것으로 봅니다.
+    pub fn validate(
+        &self,
+        sample: Option<usize>,
+        repair: bool,
+        rescan: bool,
+    ) -> Result<ValidationReport, String> {
+        // 루트와 루트 바로 아래의 NTFS 메타 파일($MFT 등)은 일반적인 방법으로 조회되지 않으므로 제외
+        let mut frns: Vec<u64> = self
+            .entries
+            .iter()
+            .filter(|r| {
+                !is_root_frn(*r.key())
+                    && !(is_root_frn(r.value().parent_frn) && r.value().name.starts_with('$'))
+            })
+            .map(|r| *r.key())
+            .collect();
+        frns.sort_unstable();
+        let full = match sample {
+            Some(sample) if sample > 0 && sample < frns.len() => {
+                let step = frns.len() / sample;
+                frns = frns.into_iter().step_by(step).take(sample).collect();
+                false
+            }
+            _ => true,
+        };
+
+        // (FRN, 경로, 디스크에 있는지)
+        let checked: Vec<(u64, Option<PathBuf>, bool)> = frns
+            .par_iter()
+            .map(|frn| {
+                let Some(path) = self.reconstruct_path(frn) else {
+                    return (*frn, None, false);
+                };
+                let exists = match std::fs::symlink_metadata(&path) {
+                    Err(e) => e.kind() != std::io::ErrorKind::NotFound,
+                    Ok(_) => true,
+                };
+                (*frn, Some(path), exists)
+            })
+            .collect();
+
+        let mut stale = Vec::new();
+        let mut stale_paths = Vec::new();
+        for (frn, path, exists) in &checked {
+            if *exists {
+                continue;
+            }
+            stale.push(*frn);
+            match path {
+                Some(path) if stale_paths.len() < STALE_PATHS_MAX => {
+                    stale_paths.push(path.to_string_lossy().into_owned());
+                }
+                _ => {}
+            }
+        }
+
+        let mut removed = 0;
+        if repair && !stale.is_empty() {
+            for frn in &stale {
+                if self.entries.remove(frn).is_some() {
+                    removed += 1;
+                }
+            }
+            removed += self.remove_orphans();
+        }
+
+        let mut added = 0;
+        if rescan {
+            let mut dirs: Vec<(u64, PathBuf)> = checked
+                .into_iter()
+                .filter(|(frn, _, exists)| {
+                    *exists && self.entries.get(frn).is_some_and(|e| e.is_dir)
+                })
+                .filter_map(|(frn, path, _)| Some((frn, path?)))
+                .collect();
+            if full {
+                let root_frn = self
+                    .entries
+                    .iter()
+                    .map(|r| *r.key())
+                    .find(|frn| is_root_frn(*frn))
+                    .unwrap_or(ROOT_FRN);
+                dirs.push((root_frn, drive_root(&self.drive_letter)));
+            }
+            added = self.rescan_dirs(&dirs);
+        }
+
+        if removed > 0 || added > 0 {
+            self.rebuild_search_index()?;
+        }
+        Ok(ValidationReport {
+            checked: frns.len(),
+            stale: stale.len(),
+            removed,
+            added,
+            stale_paths,
+        })
+    }
+
+    // 상위 폴더를 따라 루트까지 올라갈 수 없는 항목을 지우고 개수를 반환
+    fn remove_orphans(&self) -> usize {
+        let parents: HashMap<u64, u64> = self
+            .entries
+            .iter()
+            .map(|r| (*r.key(), r.value().parent_frn))
+            .collect();
+        let mut reachable: HashMap<u64, bool> = HashMap::new();
+        let mut orphans = Vec::new();
+        for &frn in parents.keys() {
+            let mut chain = Vec::new();
+            let mut current = frn;
+            // reconstruct_path와 같은 깊이 제한 (끝까지 확인하지 못하면 지우지 않음)
+            let mut result = true;
+            for _ in 0..self.max_path_depth.load(Ordering::Relaxed) {
+                if let Some(&known) = reachable.get(&current) {
+                    result = known;
                     break;
                 }
-                return None; // 부모 정보 유실 (삭제된 파일 등)
+                if is_root_frn(current) {
+                    break;
+                }
+                let Some(&parent) = parents.get(&current) else {
+                    result = false;
+                    break;
+                };
+                chain.push(current);
+                if parent == current || parent == 0 {
+                    break;
+                }
+                current = parent;
             }
+            for link in chain {
+                reachable.insert(link, result);
+            }
+            if !result {
+                orphans.push(frn);
+            }
+        }
+        for frn in &orphans {
+            self.entries.remove(frn);
         }
+        orphans.len()
+    }
 
-        path_parts.reverse();
-        let mut path = PathBuf::from(&self.drive_letter);
-        if !self.drive_letter.ends_with('\\') {
-            path.push("\\");
+    // 폴더들을 다시 읽어 인덱스에 없는 항목을 (하위 폴더째) 추가하고 개수를 반환
+    fn rescan_dirs(&self, dirs: &[(u64, PathBuf)]) -> usize {
+        let walk_based = self.is_walk_based();
+        let dir_frns: std::collections::HashSet<u64> = dirs.iter().map(|(frn, _)| *frn).collect();
+        // 폴더 FRN -> 인덱스에 있는 하위 항목 이름 (소문자)
+        let mut known: HashMap<u64, std::collections::HashSet<String>> = HashMap::new();
+        for r in self.entries.iter() {
+            if dir_frns.contains(&r.value().parent_frn) {
+                known
+                    .entry(r.value().parent_frn)
+                    .or_default()
+                    .insert(r.value().name.to_lowercase());
+            }
         }
-        for part in path_parts {
-            path.push(part);
+        // 폴더 탐색 인덱스는 FRN 대신 순번을 사용하므로 기존 최댓값 다음부터 할당
+        let mut next_id = self.entries.iter().map(|r| *r.key()).max().unwrap_or(15) + 1;
+
+        let mut added = 0;
+        for (dir_frn, dir_path) in dirs {
+            let Ok(children) = std::fs::read_dir(dir_path) else {
+                continue;
+            };
+            for child in children.flatten() {
+                let name = child.file_name().to_string_lossy().to_lowercase();
+                let indexed = known.get(dir_frn);
+                if indexed.is_some_and(|names| names.contains(&name)) {
+                    continue;
+                }
+                let mut ids: HashMap<PathBuf, u64> = HashMap::new();
+                for entry in walkdir::WalkDir::new(child.path()).into_iter().flatten() {
+                    let parent_frn = if entry.depth() == 0 {
+                        *dir_frn
+                    } else {
+                        match entry.path().parent().and_then(|p| ids.get(p)) {
+                            Some(frn) => *frn,
+                            None => continue,
+                        }
+                    };
+                    let frn = if walk_based {
+                        next_id += 1;
+                        next_id - 1
+                    } else {
+                        match file_reference_number(entry.path()) {
+                            Some(frn) => frn,
+                            None => continue,
+                        }
+                    };
+                    if entry.file_type().is_dir() {
+                        ids.insert(entry.path().to_path_buf(), frn);
+                    }
+                    self.entries.insert(frn, walk_entry(&entry, parent_frn));
+                    added += 1;
+                }
+            }
         }
-        Some(path)
+        added
+    }
+
+    // 저장된 USN 저널 ID (인덱스 파일 저장용)
+    pub fn journal_id(&self) -> u64 {
+        self.journal_id.load(Ordering::SeqCst)
     }
 
     // 검색 최적화를 위한 인덱스 재생성
     fn rebuild_search_index(&self) -> Result<(), String> {
+        self.dir_paths.clear();
         let mut search_idx = self.search_index.write().map_err(|e| e.to_string())?;
         *search_idx = self
             .entries
             .par_iter() // rayon을 사용해 병렬로 처리
-            .map(|r| (*r.key(), r.value().name.clone()))
+            .map(|r| SearchName::new(*r.key(), r.value().name.clone()))
             .collect();
         Ok(())
     }
 }
+
+// 여러 드라이브의 인덱스를 함께 검색합니다.
+// 각 인덱스에서 정렬된 앞쪽 offset + limit개만 받아 합친 뒤 같은 기준으로 다시 정렬하므로,
+// 페이지 순서는 하나의 인덱스를 검색할 때와 같습니다. total은 모든 드라이브의 일치 개수 합입니다.
+pub fn search_all(
+    indexes: &[Arc<MftIndex>],
+    query: &str,
+    mode: SearchMode,
+    filter: &SearchFilter,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<(Vec<SearchHit>, usize), String> {
+    let per_index = limit.map(|limit| offset.saturating_add(limit));
+    let mut hits = Vec::new();
+    let mut total = 0;
+    for index in indexes {
+        let (index_hits, index_total) = index.search(query, mode, filter, 0, per_index)?;
+        hits.extend(index_hits);
+        total += index_total;
+    }
+    hits.par_sort_unstable_by(|a, b| filter.compare_hits(a, b));
+    let hits = hits
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    Ok((hits, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 테스트용 인덱스에 항목 추가 (루트 폴더의 FRN은 ROOT_FRN)
+    fn insert(index: &MftIndex, frn: u64, parent_frn: u64, name: &str, is_dir: bool) {
+        let entry = FileEntry {
+            parent_frn,
+            name: name.to_string(),
+            is_dir,
+            size: None,
+            modified: None,
+        };
+        index.entries.insert(frn, entry);
+    }
+
+    #[test]
+    fn search_counts_every_match_and_pages_by_frn() {
+        let index = MftIndex::new("C:".to_string());
+        insert(&index, 10, ROOT_FRN, "docs", true);
+        insert(&index, 11, ROOT_FRN, "$Recycle.Bin", true);
+        insert(&index, 12, 11, "report-old.txt", false);
+        // FRN 순서와 이름 순서가 다르도록 역순으로 번호를 매김
+        for i in 0..20 {
+            insert(&index, 119 - i, 10, &format!("report{:02}.txt", i), false);
+        }
+        index.rebuild_search_index().unwrap();
+        let exclude_paths = DEFAULT_SEARCH_EXCLUSIONS.iter().map(|s| s.to_string());
+        let filter = SearchFilter {
+            exclude_paths: exclude_paths.collect(),
+            ..Default::default()
+        };
+
+        let (hits, total) = index
+            .search("report", SearchMode::Substring, &filter, 5, Some(5))
+            .unwrap();
+        assert_eq!(total, 20);
+        let paths: Vec<PathBuf> = hits.iter().map(|hit| hit.path.clone()).collect();
+        let expected: Vec<PathBuf> = (10..15)
+            .rev()
+            .map(|i| PathBuf::from(format!("C:\\docs\\report{:02}.txt", i)))
+            .collect();
+        assert_eq!(paths, expected);
+        assert!(hits.iter().all(|hit| hit.drive == "C:"));
+
+        // 제외 경로의 폴더 자체도 결과에서 빠짐
+        let (hits, total) = index
+            .search("recycle", SearchMode::Substring, &filter, 0, None)
+            .unwrap();
+        assert_eq!((hits.len(), total), (0, 0));
+    }
+
+    #[test]
+    fn join_parts_builds_paths_under_the_drive_root() {
+        for drive in ["C:", "C:\\"] {
+            let root = drive_root(drive).to_string_lossy().into_owned();
+            assert_eq!(root, "C:\\");
+            let mut dirs = Vec::new();
+            let mut record = |frn: u64, path: &str| dirs.push((frn, path.to_string()));
+
+            // 루트 자신, 루트 바로 아래 파일
+            assert_eq!(join_parts(&root, Vec::new(), &mut record), "C:\\");
+            let file = vec![(20, "file.txt".to_string(), false)];
+            assert_eq!(join_parts(&root, file, &mut record), "C:\\file.txt");
+
+            // 여러 단계 아래 파일 (가장 안쪽 항목부터), 지나온 폴더는 on_dir로 알림
+            let nested = vec![
+                (40, "d.txt".to_string(), false),
+                (32, "c".to_string(), true),
+                (31, "b".to_string(), true),
+                (30, "a".to_string(), true),
+            ];
+            assert_eq!(join_parts(&root, nested, &mut record), "C:\\a\\b\\c\\d.txt");
+            assert_eq!(
+                dirs,
+                [
+                    (30, "C:\\a".to_string()),
+                    (31, "C:\\a\\b".to_string()),
+                    (32, "C:\\a\\b\\c".to_string()),
+                ]
+            );
+        }
+
+        // 캐시된 폴더 경로에서 이어 붙이기
+        let cached = vec![(50, "e.txt".to_string(), false)];
+        assert_eq!(join_parts("D:\\a\\b", cached, |_, _| {}), "D:\\a\\b\\e.txt");
+    }
+}
@@ -0,0 +1,57 @@
+// 암호화용 비밀번호 검사와 강도 추정
+//
+// 강도는 사용한 문자 종류로 문자 집합 크기를 정하고, 길이(반복 문자는 적게 셈)를 곱해
+// 대략적인 엔트로피(비트)를 계산한 뒤 0-4 점수로 나눕니다.
+// 사전 공격을 정확히 막는 것이 목적이 아니라, "1234"처럼 거의 보호가 되지 않는 비밀번호를 알려주기 위한 것입니다.
+
+// 흔히 쓰이는 비밀번호 (대소문자 무시, 일치하면 항상 0점)
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "passw0rd", "123456", "1234567", "12345678", "123456789", "1234567890",
+    "qwerty", "qwerty123", "abc123", "111111", "000000", "iloveyou", "admin", "letmein",
+    "welcome", "monkey", "dragon", "1q2w3e4r", "asdfgh",
+];
+
+// min_length보다 짧거나 비어 있는 비밀번호를 거부합니다 ("PASSWORD_TOO_SHORT: <최소 길이>").
+// 길이는 바이트가 아닌 문자 수로 셉니다.
+pub fn check_length(password: &str, min_length: usize) -> Result<(), String> {
+    let min_length = min_length.max(1);
+    if password.chars().count() < min_length {
+        return Err(format!("PASSWORD_TOO_SHORT: {}", min_length));
+    }
+    Ok(())
+}
+
+// 비밀번호 강도 점수 (0: 매우 약함 ~ 4: 매우 강함)
+pub fn estimate_strength(password: &str) -> u8 {
+    if password.is_empty() || COMMON_PASSWORDS.contains(&password.to_lowercase().as_str()) {
+        return 0;
+    }
+
+    let has = |f: fn(&char) -> bool| password.chars().any(|c| f(&c));
+    let charset = [
+        (has(char::is_ascii_lowercase), 26),
+        (has(char::is_ascii_uppercase), 26),
+        (has(char::is_ascii_digit), 10),
+        (has(char::is_ascii_punctuation) || password.contains(' '), 33),
+        (has(|c| !c.is_ascii()), 100),
+    ]
+    .iter()
+    .filter(|(used, _)| *used)
+    .map(|(_, size)| size)
+    .sum::<u32>();
+
+    // "aaaaaaaa"처럼 같은 문자를 반복하면 길이만큼 강해지지 않으므로, 길이를 서로 다른 문자 수의 2배로 제한
+    let mut unique: Vec<char> = password.chars().collect();
+    unique.sort_unstable();
+    unique.dedup();
+    let length = password.chars().count().min(unique.len() * 2);
+
+    let bits = length as f64 * (charset as f64).log2();
+    match bits {
+        b if b < 28.0 => 0,
+        b if b < 36.0 => 1,
+        b if b < 60.0 => 2,
+        b if b < 128.0 => 3,
+        _ => 4,
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// 압축 해제 이어하기 기록 (<대상 폴더>/.<zip 이름>.mhzipy-resume)
+//
+// 첫 줄에 아카이브 식별 정보(크기, 수정 시간, 항목 수)를, 이후 항목 하나를 끝낼 때마다
+// 완료한 항목 번호를 JSON 한 줄로 추가합니다. 항목 번호는 중앙 디렉터리 순서이므로
+// 같은 아카이브라면 다시 열어도 같은 항목을 가리킵니다. 기록 도중 꺼져 마지막 줄이
+// 잘렸으면 그 줄은 무시합니다.
+
+#[derive(Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ArchiveFingerprint {
+    size: u64,
+    modified: u64,
+    entries: usize,
+}
+
+impl ArchiveFingerprint {
+    pub fn of(zip_path: &Path, entries: usize) -> io::Result<Self> {
+        let meta = fs::metadata(zip_path)?;
+        let modified = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Ok(Self {
+            size: meta.len(),
+            modified,
+            entries,
+        })
+    }
+}
+
+// 완료한 항목 (폴더이거나 정책으로 건너뛴 항목은 path가 None)
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Completed {
+    index: usize,
+    path: Option<PathBuf>,
+    size: u64,
+}
+
+impl Completed {
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    // 기록한 파일이 아직 같은 크기로 남아 있는지 (지워졌거나 바뀌었으면 다시 풀어야 함)
+    pub fn is_intact(&self) -> bool {
+        match &self.path {
+            Some(path) => fs::metadata(path).is_ok_and(|m| m.is_file() && m.len() == self.size),
+            None => true,
+        }
+    }
+}
+
+pub struct ResumeLog {
+    file: File,
+}
+
+impl ResumeLog {
+    // 새 기록 시작 (이전 기록은 덮어씀)
+    pub fn create(path: &Path, fingerprint: ArchiveFingerprint) -> io::Result<Self> {
+        let mut file = File::create(path)?;
+        writeln!(file, "{}", serde_json::to_string(&fingerprint)?)?;
+        Ok(Self { file })
+    }
+
+    // 기존 기록에 이어서 추가
+    pub fn append(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().append(true).open(path)?;
+        Ok(Self { file })
+    }
+
+    pub fn record(&mut self, index: usize, path: Option<&Path>, size: u64) -> io::Result<()> {
+        let entry = Completed {
+            index,
+            path: path.map(Path::to_path_buf),
+            size,
+        };
+        writeln!(self.file, "{}", serde_json::to_string(&entry)?)
+    }
+}
+
+// 기록을 읽어 완료한 항목을 항목 번호별로 반환
+// 기록이 없으면 RESUME_NOT_FOUND, 다른 아카이브(또는 바뀐 아카이브)의 기록이면 RESUME_MISMATCH 오류
+pub fn load(
+    path: &Path,
+    fingerprint: ArchiveFingerprint,
+) -> Result<HashMap<usize, Completed>, String> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err("RESUME_NOT_FOUND".into()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut lines = BufReader::new(file).lines();
+    let header = lines
+        .next()
+        .transpose()
+        .map_err(|e| e.to_string())?
+        .and_then(|line| serde_json::from_str::<ArchiveFingerprint>(&line).ok());
+    if header != Some(fingerprint) {
+        return Err("RESUME_MISMATCH".into());
+    }
+    let mut completed = HashMap::new();
+    for line in lines {
+        let line = line.map_err(|e| e.to_string())?;
+        if let Ok(entry) = serde_json::from_str::<Completed>(&line) {
+            completed.insert(entry.index, entry);
+        }
+    }
+    Ok(completed)
+}
+
+// 대상 폴더 안의 기록 파일 경로 (archive.zip -> <대상 폴더>/.archive.zip.mhzipy-resume)
+pub fn path_for(target_dir: &Path, zip_path: &Path) -> PathBuf {
+    let name = zip_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target_dir.join(format!(".{}.mhzipy-resume", name))
+}
+
+// 쓰는 중인 파일의 임시 경로 (다 쓴 뒤 원래 이름으로 바꾸므로, 중간에 꺼져도 덜 쓴 파일이 남지 않음)
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".mhzipy-part");
+    PathBuf::from(name)
+}
@@ -0,0 +1,148 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::split;
+
+// 자동 압축 해제(SFX) 실행 파일 생성
+//
+// SFX 파일은 [스텁 실행 파일][ZIP] 형태로 단순히 이어 붙인 것입니다.
+// 스텁(sfx-stub 크레이트)은 실행되면 자기 자신을 ZIP으로 열어 내용을 풀어냅니다.
+// ZIP 앞에 스텁이 붙으면 중앙 디렉터리에 기록된 오프셋이 스텁 길이만큼 어긋나므로,
+// 중앙 디렉터리의 로컬 헤더 오프셋과 EOCD의 중앙 디렉터리 오프셋을 스텁 길이만큼 보정합니다.
+// 보정된 파일은 스텁을 인식하지 못하는 일반 ZIP 도구에서도 그대로 열립니다.
+// 암호화는 항목 단위로 ZIP 안에 그대로 남아 있으므로 스텁이 실행 시 비밀번호를 묻습니다.
+
+pub const STUB_FILE_NAME: &str = "sfx_stub.exe";
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const EOCD_SIZE: usize = 22;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const ZIP64_LOCATOR_SIZE: u64 = 20;
+
+// 스텁 경로: 번들 리소스 폴더를 먼저 보고, 없으면 실행 파일 옆에서 찾습니다.
+pub fn find_stub(resource_dir: Option<PathBuf>) -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf));
+    [resource_dir, exe_dir]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join(STUB_FILE_NAME))
+        .find(|p| p.is_file())
+}
+
+fn read_u16(buf: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([buf[at], buf[at + 1]])
+}
+
+fn read_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([buf[at], buf[at + 1], buf[at + 2], buf[at + 3]])
+}
+
+fn write_u32(buf: &mut [u8], at: usize, value: u32) {
+    buf[at..at + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+// 파일 끝에서 EOCD 레코드 위치를 찾음 (주석은 최대 65535바이트)
+fn find_eocd(file: &mut File, len: u64) -> io::Result<(u64, Vec<u8>)> {
+    let tail_len = len.min((EOCD_SIZE + u16::MAX as usize) as u64);
+    let tail_start = len - tail_len;
+    let mut tail = vec![0u8; tail_len as usize];
+    file.seek(SeekFrom::Start(tail_start))?;
+    file.read_exact(&mut tail)?;
+
+    let last = tail
+        .len()
+        .checked_sub(EOCD_SIZE)
+        .ok_or_else(|| invalid("Not a ZIP archive"))?;
+    for at in (0..=last).rev() {
+        if read_u32(&tail, at) == EOCD_SIGNATURE
+            && at + EOCD_SIZE + read_u16(&tail, at + 20) as usize == tail.len()
+        {
+            return Ok((tail_start + at as u64, tail[at..].to_vec()));
+        }
+    }
+    Err(invalid("Not a ZIP archive"))
+}
+
+// stub + zip을 output에 쓰고 오프셋을 보정합니다.
+// <output>.part에 쓴 뒤 성공했을 때만 이름을 바꾸므로, 실패해도 기존 output은 그대로 남습니다.
+pub fn create(stub: &Path, zip_path: &Path, output: &Path) -> io::Result<u64> {
+    let mut zip = File::open(zip_path)?;
+    let zip_len = zip.metadata()?.len();
+    let (eocd_pos, mut eocd) = find_eocd(&mut zip, zip_len)?;
+
+    let disk = read_u16(&eocd, 4);
+    let entry_count = read_u16(&eocd, 10);
+    let cd_size = read_u32(&eocd, 12);
+    let cd_offset = read_u32(&eocd, 16);
+    if disk != 0 {
+        return Err(invalid("Split archives cannot be converted to SFX"));
+    }
+
+    // ZIP64 레코드까지 보정하는 것은 지원하지 않음 (4GB 이상 또는 65535개 이상의 항목)
+    let has_zip64_locator = eocd_pos >= ZIP64_LOCATOR_SIZE && {
+        let mut sig = [0u8; 4];
+        zip.seek(SeekFrom::Start(eocd_pos - ZIP64_LOCATOR_SIZE))?;
+        zip.read_exact(&mut sig)?;
+        u32::from_le_bytes(sig) == ZIP64_LOCATOR_SIGNATURE
+    };
+    if has_zip64_locator || cd_offset == u32::MAX || entry_count == u16::MAX {
+        return Err(invalid("ZIP64 archives cannot be converted to SFX"));
+    }
+    if cd_offset as u64 + cd_size as u64 != eocd_pos {
+        return Err(invalid("Archive already has data before the first entry"));
+    }
+
+    let stub_len = std::fs::metadata(stub)?.len();
+    let shift = |offset: u32| -> io::Result<u32> {
+        u32::try_from(offset as u64 + stub_len)
+            .map_err(|_| invalid("Archive is too large to convert to SFX"))
+    };
+
+    // 중앙 디렉터리를 읽어 각 항목의 로컬 헤더 오프셋 보정
+    let mut central = vec![0u8; cd_size as usize];
+    zip.seek(SeekFrom::Start(cd_offset as u64))?;
+    zip.read_exact(&mut central)?;
+    let mut at = 0;
+    for _ in 0..entry_count {
+        if at + CENTRAL_HEADER_SIZE > central.len()
+            || read_u32(&central, at) != CENTRAL_HEADER_SIGNATURE
+        {
+            return Err(invalid("Corrupted central directory"));
+        }
+        let local_offset = read_u32(&central, at + 42);
+        if local_offset == u32::MAX {
+            return Err(invalid("ZIP64 archives cannot be converted to SFX"));
+        }
+        write_u32(&mut central, at + 42, shift(local_offset)?);
+        at += CENTRAL_HEADER_SIZE
+            + read_u16(&central, at + 28) as usize
+            + read_u16(&central, at + 30) as usize
+            + read_u16(&central, at + 32) as usize;
+    }
+    write_u32(&mut eocd, 16, shift(cd_offset)?);
+
+    let temp_path = split::temp_path(output);
+    let written = (|| -> io::Result<()> {
+        let mut out = File::create(&temp_path)?;
+        io::copy(&mut File::open(stub)?, &mut out)?;
+        zip.seek(SeekFrom::Start(0))?;
+        io::copy(&mut (&mut zip).take(cd_offset as u64), &mut out)?;
+        out.write_all(&central)?;
+        out.write_all(&eocd)?;
+        out.flush()
+    })();
+    if let Err(e) = written.and_then(|_| std::fs::rename(&temp_path, output)) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    Ok(stub_len + zip_len)
+}
@@ -0,0 +1,164 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// 분할 압축 조각의 최소 크기 (너무 작은 조각은 파일 수만 늘어남)
+pub const MIN_SPLIT_SIZE: u64 = 64 * 1024;
+
+// 분할 압축(archive.zip.001, archive.zip.002, ...) 쓰기용 Writer
+//
+// ZipWriter는 로컬 헤더를 나중에 다시 쓰기 위해 Seek가 필요하므로,
+// 전역 오프셋을 (조각 번호, 조각 내 오프셋)으로 변환해 모든 조각 파일을 열어둔 채로 씁니다.
+// 조각은 ZIP 분할 헤더가 없는 단순 바이트 분할(7-Zip 등과 같은 .001 방식)이므로
+// .001, .002, ... 순서로 이어 붙이면 일반 ZIP이 됩니다 (join_parts 참고).
+// split_size가 None이면 임시 파일(<대상>.part) 하나에, 분할이면 조각마다 <조각>.part에 씁니다.
+// 어느 쪽이든 finish_parts에서 이름을 바꾸기 전까지 최종 경로에는 아무것도 생기지 않으므로,
+// 중간에 실패하거나 프로세스가 종료되어도 깨진 ZIP이나 빠진 조각이 최종 이름으로 남지 않습니다.
+pub struct SplitWriter {
+    target: PathBuf,
+    split_size: Option<u64>,
+    parts: Vec<File>,
+    pos: u64,
+    len: u64,
+}
+
+impl SplitWriter {
+    pub fn new(target: &Path, split_size: Option<u64>) -> Self {
+        Self {
+            target: target.to_path_buf(),
+            split_size,
+            parts: Vec::new(),
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    fn part(&mut self, index: usize) -> io::Result<&mut File> {
+        while self.parts.len() <= index {
+            let path = match self.split_size {
+                Some(_) => temp_path(&part_path(&self.target, self.parts.len() + 1)),
+                None => temp_path(&self.target),
+            };
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(path)?;
+            self.parts.push(file);
+        }
+        Ok(&mut self.parts[index])
+    }
+}
+
+impl Write for SplitWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let (index, offset, n) = match self.split_size {
+            Some(size) => {
+                let offset = self.pos % size;
+                let n = buf.len().min((size - offset) as usize);
+                ((self.pos / size) as usize, offset, n)
+            }
+            None => (0, self.pos, buf.len()),
+        };
+
+        let file = self.part(index)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let written = file.write(&buf[..n])?;
+
+        self.pos += written as u64;
+        self.len = self.len.max(self.pos);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        for file in &mut self.parts {
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Seek for SplitWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(p) => Some(p),
+            SeekFrom::Current(d) => self.pos.checked_add_signed(d),
+            SeekFrom::End(d) => self.len.checked_add_signed(d),
+        };
+        self.pos = new_pos.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative position")
+        })?;
+        Ok(self.pos)
+    }
+}
+
+// n번째 조각 경로 (archive.zip -> archive.zip.001, archive.zip.002, ...)
+pub fn part_path(target: &Path, n: usize) -> PathBuf {
+    let mut name = target.as_os_str().to_owned();
+    name.push(format!(".{:03}", n));
+    PathBuf::from(name)
+}
+
+// 분할하지 않을 때 쓰는 임시 파일 경로 (archive.zip -> archive.zip.part)
+pub fn temp_path(target: &Path) -> PathBuf {
+    let mut name = target.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+// 쓰기가 끝난 뒤 임시 파일(분할이면 모든 조각)을 최종 이름으로 바꿉니다.
+// 반환값은 조각 수 (분할하지 않았으면 1)
+pub fn finish_parts(target: &Path, total_len: u64, split_size: Option<u64>) -> io::Result<usize> {
+    let Some(size) = split_size else {
+        fs::rename(temp_path(target), target)?;
+        return Ok(1);
+    };
+    let count = total_len.div_ceil(size).max(1) as usize;
+    for n in 1..=count {
+        let part = part_path(target, n);
+        fs::rename(temp_path(&part), part)?;
+    }
+    Ok(count)
+}
+
+// 취소/실패 시 생성된 임시 파일과 조각을 모두 삭제합니다.
+// 최종 경로는 finish_parts 전에는 만들어지지 않으므로 기존 파일을 지우지 않도록 건드리지 않습니다.
+pub fn remove_parts(target: &Path) {
+    let _ = fs::remove_file(temp_path(target));
+    let mut n = 1;
+    while fs::remove_file(temp_path(&part_path(target, n))).is_ok() {
+        n += 1;
+    }
+}
+
+// 분할 조각 경로(archive.zip.001 등, 몇 번째 조각이든)면 원래 대상 경로(archive.zip)를 반환
+pub fn split_target(path: &Path) -> Option<PathBuf> {
+    let ext = path.extension()?.to_str()?;
+    if ext.len() != 3 || !ext.bytes().all(|b| b.is_ascii_digit()) || ext == "000" {
+        return None;
+    }
+    let target = path.with_extension("");
+    part_path(&target, 1).is_file().then_some(target)
+}
+
+// .001부터 이어지는 조각을 순서대로 dest 하나로 합칩니다. 반환값은 조각 수
+pub fn join_parts(target: &Path, dest: &Path) -> io::Result<usize> {
+    let mut out = File::create(dest)?;
+    let mut count = 0;
+    while let Ok(mut part) = File::open(part_path(target, count + 1)) {
+        io::copy(&mut part, &mut out)?;
+        count += 1;
+    }
+    if count == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no split parts found",
+        ));
+    }
+    out.flush()?;
+    Ok(count)
+}
@@ -0,0 +1,255 @@
+use std::cell::Cell;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use crate::bomb::{BombGuard, GuardedReader};
+use crate::{safe_output_path, PlannedEntry, ProgressEmitter};
+
+// tar / tar.gz 아카이브와 단일 파일 .gz 처리
+// ZIP 경로와 같은 ProgressEmitter를 사용하므로 프론트엔드는 같은 진행률 이벤트를 받습니다.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TarKind {
+    Tar,
+    TarGz,
+}
+
+// 확장자로 tar 형식 판별 (.tar, .tar.gz, .tgz), tar가 아니면 None
+pub fn detect(path: &Path) -> Option<TarKind> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(TarKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(TarKind::Tar)
+    } else {
+        None
+    }
+}
+
+// gzip 압축 레벨 검증 (0-9, 기본값 6)
+pub fn gzip_level(level: Option<i64>) -> Result<flate2::Compression, String> {
+    match level {
+        None => Ok(flate2::Compression::default()),
+        Some(l @ 0..=9) => Ok(flate2::Compression::new(l as u32)),
+        Some(l) => Err(format!("Compression level {} is out of range (0-9)", l)),
+    }
+}
+
+// 읽은 바이트 수를 세는 Reader (gzip은 압축 해제 후 크기를 미리 알 수 없으므로,
+// 해제 진행률은 원본 아카이브 파일을 얼마나 읽었는지로 계산)
+struct CountingReader<R> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.set(self.count.get() + n as u64);
+        Ok(n)
+    }
+}
+
+// tar 아카이브를 target_dir에 해제합니다. progress의 total은 아카이브 파일 크기여야 합니다.
+// 심볼릭 링크, 하드 링크, 장치 파일 같은 특수 항목은 대상 폴더 밖을 가리킬 수 있으므로 건너뜁니다.
+// 해제한 바이트는 guard에 누적하며, 해제한 파일 경로 목록을 반환합니다.
+pub fn extract(
+    archive_path: &Path,
+    kind: TarKind,
+    target_dir: &Path,
+    progress: &mut ProgressEmitter,
+    guard: &mut BombGuard,
+) -> Result<Vec<PathBuf>, String> {
+    let count = Rc::new(Cell::new(0u64));
+    let reader = CountingReader {
+        inner: BufReader::new(File::open(archive_path).map_err(|e| e.to_string())?),
+        count: count.clone(),
+    };
+    let reader: Box<dyn Read> = match kind {
+        TarKind::Tar => Box::new(reader),
+        TarKind::TarGz => Box::new(flate2::read::GzDecoder::new(reader)),
+    };
+    let mut archive = tar::Archive::new(reader);
+    fs::create_dir_all(target_dir).map_err(|e| e.to_string())?;
+
+    let mut extracted = Vec::new();
+    let mut reported = 0;
+    for entry in archive.entries().map_err(|e| e.to_string())? {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.path().map_err(|e| e.to_string())?;
+        let name = name.to_string_lossy().replace('\\', "/");
+        let outpath = safe_output_path(target_dir, &name)?;
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            fs::create_dir_all(&outpath).map_err(|e| e.to_string())?;
+        } else if entry_type.is_file() {
+            if let Some(p) = outpath.parent() {
+                fs::create_dir_all(p).map_err(|e| e.to_string())?;
+            }
+            let mut outfile = File::create(&outpath).map_err(|e| e.to_string())?;
+            io::copy(&mut GuardedReader::new(&mut entry, guard), &mut outfile)
+                .map_err(|e| e.to_string())?;
+            if let Ok(mtime) = entry.header().mtime() {
+                let _ = outfile.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(mtime));
+            }
+            extracted.push(outpath);
+        }
+
+        progress.advance(count.get() - reported, &name)?;
+        reported = count.get();
+    }
+    progress.complete(Some(target_dir.to_string_lossy().into_owned()))?;
+    Ok(extracted)
+}
+
+// 원본 파일을 읽으면서 진행률을 보고하고 취소 요청을 확인하는 Reader
+struct ProgressReader<'a, 'b, R> {
+    inner: R,
+    name: &'a str,
+    progress: &'a mut ProgressEmitter<'b>,
+    cancel: &'a AtomicBool,
+}
+
+impl<R: Read> Read for ProgressReader<'_, '_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.cancel.load(Ordering::Relaxed) {
+            return Err(io::Error::other("CANCELLED"));
+        }
+        let n = self.inner.read(buf)?;
+        self.progress
+            .advance(n as u64, self.name)
+            .map_err(io::Error::other)?;
+        Ok(n)
+    }
+}
+
+// 계획된 항목들을 .tar.gz로 압축하고 전체 출력 크기를 반환합니다.
+// 취소되면 CANCELLED 오류를 반환하며, 불완전한 파일 삭제는 호출하는 쪽에서 처리합니다.
+pub fn create_tar_gz(
+    target: &Path,
+    entries: &[PlannedEntry],
+    level: flate2::Compression,
+    progress: &mut ProgressEmitter,
+    cancel: &AtomicBool,
+) -> Result<u64, String> {
+    let file = File::create(target).map_err(|e| e.to_string())?;
+    let encoder = flate2::write::GzEncoder::new(BufWriter::new(file), level);
+    let mut builder = tar::Builder::new(encoder);
+
+    for entry in entries {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("CANCELLED".to_string());
+        }
+        if entry.is_dir {
+            builder
+                .append_dir(entry.name.as_str(), &entry.source)
+                .map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        let file = File::open(&entry.source).map_err(|e| e.to_string())?;
+        let metadata = file.metadata().map_err(|e| e.to_string())?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(&metadata);
+        header.set_size(entry.size);
+        let reader = ProgressReader {
+            inner: BufReader::new(file).take(entry.size),
+            name: &entry.name,
+            progress: &mut *progress,
+            cancel,
+        };
+        // append_data는 긴 이름을 GNU 확장 헤더로 기록
+        builder
+            .append_data(&mut header, entry.name.as_str(), reader)
+            .map_err(|e| {
+                if cancel.load(Ordering::Relaxed) {
+                    "CANCELLED".to_string()
+                } else {
+                    e.to_string()
+                }
+            })?;
+    }
+
+    let mut writer = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())?;
+    drop(writer);
+    Ok(fs::metadata(target).map_err(|e| e.to_string())?.len())
+}
+
+// 단일 파일을 .gz로 압축하고 출력 크기를 반환합니다. (tar 컨테이너 없이 gzip 스트림만 기록)
+pub fn gzip_file(
+    src: &Path,
+    dest: &Path,
+    level: flate2::Compression,
+    progress: &mut ProgressEmitter,
+    cancel: &AtomicBool,
+) -> Result<u64, String> {
+    let name = src
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut reader = ProgressReader {
+        inner: BufReader::new(File::open(src).map_err(|e| e.to_string())?),
+        name: &name,
+        progress: &mut *progress,
+        cancel,
+    };
+    // gzip 헤더에 원래 파일 이름을 기록 (gunzip -N 등에서 사용)
+    let mut encoder = flate2::GzBuilder::new().filename(name.as_bytes()).write(
+        BufWriter::new(File::create(dest).map_err(|e| e.to_string())?),
+        level,
+    );
+    io::copy(&mut reader, &mut encoder).map_err(|e| {
+        if cancel.load(Ordering::Relaxed) {
+            "CANCELLED".to_string()
+        } else {
+            e.to_string()
+        }
+    })?;
+    encoder
+        .finish()
+        .and_then(|mut writer| writer.flush())
+        .map_err(|e| e.to_string())?;
+    Ok(fs::metadata(dest).map_err(|e| e.to_string())?.len())
+}
+
+// .gz 파일을 풀어 dest에 쓰고 해제된 크기를 반환합니다.
+// 진행률은 extract와 같이 원본 .gz 파일을 읽은 양으로 계산합니다.
+pub fn gunzip_file(src: &Path, dest: &Path, progress: &mut ProgressEmitter) -> Result<u64, String> {
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let count = Rc::new(Cell::new(0u64));
+    let reader = CountingReader {
+        inner: BufReader::new(File::open(src).map_err(|e| e.to_string())?),
+        count: count.clone(),
+    };
+    // 여러 gzip 멤버가 이어 붙은 파일도 하나로 해제
+    let mut decoder = flate2::read::MultiGzDecoder::new(reader);
+    let mut writer = BufWriter::new(File::create(dest).map_err(|e| e.to_string())?);
+
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut written = 0u64;
+    let mut reported = 0u64;
+    loop {
+        let n = decoder.read(&mut buffer).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..n]).map_err(|e| e.to_string())?;
+        written += n as u64;
+        progress.advance(count.get() - reported, &name)?;
+        reported = count.get();
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(written)
+}
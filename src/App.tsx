@@ -6,7 +6,7 @@ import FileExplorer from './FileExplorer/FileExplorer';
 import PreferenceDialog from './Menu/PreferenceDialog';
 import { ensureDir } from './utils/fileOps';
 import { invoke } from '@tauri-apps/api/core';
-import { emit } from '@tauri-apps/api/event';
+import { emit, listen } from '@tauri-apps/api/event';
 import { useTranslation } from 'react-i18next';
 import './App.css';
 
@@ -18,6 +18,7 @@ export interface SearchConfig {
   dateAfter: string;
   dateBefore: string;
   showFilters: boolean;
+  showSystem?: boolean;
   searchMode: 'index' | 'directory';
   directorySearchPaths: string[];
 }
@@ -163,9 +164,8 @@ function App() {
     }
   };
 
-  // 앱 실행 시 백그라운드로 MFT 인덱싱을 자동 실행 (비동기, UI 차단 없음)
-  useEffect(() => {
-    if (!configLoaded) return;
+  // 백그라운드 MFT 인덱싱 실행 (비동기, UI 차단 없음)
+  const runBackgroundIndexing = () => {
     // invoke를 기다리지 않고 백그라운드에서 진행시키되, 완료/오류는 로그로 남김
     invoke<number>('build_mft_index')
       .then((count) => {
@@ -186,9 +186,27 @@ function App() {
           console.warn('Failed to persist or emit index-ready flag after failure:', e);
         }
       });
+  };
+
+  // 앱 실행 시 백그라운드로 MFT 인덱싱을 자동 실행
+  useEffect(() => {
+    if (!configLoaded) return;
+    runBackgroundIndexing();
     // 의도적으로 의존성은 빈 배열이나 configLoaded로 제어: 한번만 실행
   }, [configLoaded]);
 
+  // USN 저널이 끊겨 인덱스가 디스크와 어긋나면 다시 인덱싱
+  useEffect(() => {
+    const unlistenPromise = listen<string>('index-stale', (event) => {
+      console.warn('MFT index is stale, rebuilding:', event.payload);
+      localStorage.setItem('mft_index_ready', 'false');
+      runBackgroundIndexing();
+    });
+    return () => {
+      unlistenPromise.then(unlisten => unlisten());
+    };
+  }, []);
+
   const handleFileExplorerNavigate = (path: string) => {
     if (path === currentPath) return;
 
@@ -8,6 +8,7 @@ import FileList from "./FileList";
 import SearchView from "../SearchView/SearchView";
 import { deleteFiles, pasteFiles, checkPathPermission } from '../command/fileOperations';
 import MessageDialog from "../common/MessageDialog";
+import { errorMessage } from '../utils/commandError';
 import ConfirmDialog from "../common/ConfirmDialog";
 import "./FileExplorer.css";
 import { SearchConfig } from "../App";
@@ -423,7 +424,7 @@ export default function FileExplorer({ config, onSaveConfig, currentView, search
       setRefreshTrigger(p => p + 1);
     } catch (error) {
       console.error('Compression failed:', error);
-      showMessage('Compression Failed', String(error));
+      showMessage('Compression Failed', errorMessage(error));
     }
   };
 
@@ -11,6 +11,7 @@ import { checkPathPermission } from '../command/fileOperations';
 import { openPdfInWindow } from '../PDFViewer/PDFViewer';
 import MessageDialog from '../common/MessageDialog';
 import ConfirmDialog from '../common/ConfirmDialog';
+import { errorCode, errorMessage, isPasswordError } from '../utils/commandError';
 
 interface FileListProps {
   path: string | null;
@@ -64,6 +65,9 @@ interface FilterConfig {
   unit: number; // for size multiplier
 }
 
+// extract_zip이 tar로 해제하는 확장자
+const isTarArchive = (path: string) => /\.(tar|tar\.gz|tgz)$/i.test(path);
+
 const COLUMN_LABELS: Record<string, string> = {
   name: 'Name',
   size: 'Size',
@@ -136,7 +140,7 @@ export default function FileList({
   const [isZipEncrypted, setIsZipEncrypted] = useState(false);
   const [showPassword, setShowPassword] = useState(false);
   const [extractError, setExtractError] = useState<string | null>(null);
-  const [extractProgress, setExtractProgress] = useState<{ total: number; processed: number; filename: string; startTime: number } | null>(null);
+  const [extractProgress, setExtractProgress] = useState<{ total: number; processed: number; filename: string; bytesPerSec: number; etaSecs: number | null } | null>(null);
   const [extractProgressPos, setExtractProgressPos] = useState({ x: 300, y: 300 });
 
   // Error dialog state
@@ -155,9 +159,11 @@ export default function FileList({
   const [compressName, setCompressName] = useState('archive.zip');
   const [compressMethod, setCompressMethod] = useState('deflated');
   const [compressPassword, setCompressPassword] = useState('');
+  const [passwordStrength, setPasswordStrength] = useState<number | null>(null);
   const [compressEncryption, setCompressEncryption] = useState('zipcrypto');
   const [compressTargetDir, setCompressTargetDir] = useState('');
-  const [compressProgress, setCompressProgress] = useState<{ total: number; processed: number; filename: string; startTime: number } | null>(null);
+  const [operationPaused, setOperationPaused] = useState(false);
+  const [compressProgress, setCompressProgress] = useState<{ total: number; processed: number; filename: string; bytesPerSec: number; etaSecs: number | null } | null>(null);
 
   // Permission/Message Dialog State
   const [msgDialogOpen, setMsgDialogOpen] = useState(false);
@@ -221,6 +227,19 @@ export default function FileList({
   const prevZipDialogOpen = useRef(zipDialogOpen);
   const prevPathRef = useRef<string | null>(null);
 
+  // 압축 비밀번호 강도 실시간 표시
+  useEffect(() => {
+    if (!compressPassword) {
+      setPasswordStrength(null);
+      return;
+    }
+    let cancelled = false;
+    invoke<number>('estimate_password_strength', { password: compressPassword })
+      .then(score => { if (!cancelled) setPasswordStrength(score); })
+      .catch(() => { if (!cancelled) setPasswordStrength(null); });
+    return () => { cancelled = true; };
+  }, [compressPassword]);
+
   useEffect(() => {
     if (prevCompressDialogOpen.current && !compressDialogOpen) {
       rootRef.current?.focus();
@@ -764,7 +783,7 @@ export default function FileList({
       console.error('Failed to create folder:', error);
       setErrorDialogTitle('Create Folder Failed');
       setErrorDialogMessage('Could not create folder.');
-      setErrorDialogDetails(String(error));
+      setErrorDialogDetails(errorMessage(error));
       setErrorDialogOpen(true);
     }
   };
@@ -777,7 +796,7 @@ export default function FileList({
   const canExtractHere = useMemo(() => {
     if (selectedFiles.size !== 1) return false;
     const filePath = Array.from(selectedFiles)[0];
-    if (!filePath.toLowerCase().endsWith('.zip')) return false;
+    if (!filePath.toLowerCase().endsWith('.zip') && !isTarArchive(filePath)) return false;
     return checkPathPermission(filePath, editableFolders, readonlyFolders);
   }, [selectedFiles, editableFolders, readonlyFolders]);
 
@@ -866,6 +885,12 @@ export default function FileList({
     })();
   };
 
+  // 진행 중인 압축/해제 일시 정지 및 재개
+  const toggleOperationPause = async () => {
+    await invoke(operationPaused ? 'resume_operation' : 'pause_operation');
+    setOperationPaused(!operationPaused);
+  };
+
   const performExtract = async () => {
     if (selectedFiles.size !== 1) return;
     const fullPath = Array.from(selectedFiles)[0];
@@ -879,9 +904,27 @@ export default function FileList({
       return;
     }
 
+    // tar는 암호화가 없으므로 바로 현재 폴더에 해제
+    if (isTarArchive(fullPath)) {
+      try {
+        await invoke('extract_zip', { zipPath: fullPath, targetDir: await dirname(fullPath) });
+        setVersion(v => v + 1);
+        if (onRefresh) {
+          setTimeout(() => onRefresh(), 1000);
+        }
+      } catch (error) {
+        setErrorDialogTitle('Extraction Failed');
+        setErrorDialogMessage('An error occurred during extraction. Please check the details below.');
+        setErrorDialogDetails(errorMessage(error));
+        setErrorDialogOpen(true);
+      }
+      setContextMenu(null);
+      return;
+    }
+
     try {
       // Check if ZIP requires a password
-      const entries = await invoke<{ isEncrypted: boolean }[]>('list_zip_contents', { zipPath: fullPath });
+      const { entries } = await invoke<{ entries: { isEncrypted: boolean }[] }>('list_zip_contents', { zipPath: fullPath });
       const isEncrypted = entries.some(e => e.isEncrypted);
 
       if (isEncrypted) {
@@ -928,13 +971,13 @@ export default function FileList({
       setContextMenu(null);
     } catch (e) {
       console.error("Failed to read directory", e);
-      showMessage('Error', `Failed to read directory: ${e}`);
+      showMessage('Error', `Failed to read directory: ${errorMessage(e)}`);
     }
   };
 
   const openZipFile = async (file: FileData) => {
     try {
-      const entries = await invoke<{ name: string; isDir: boolean; size: number; isEncrypted: boolean }[]>('list_zip_contents', { zipPath: file.path });
+      const { entries } = await invoke<{ entries: { name: string; isDir: boolean; size: number; isEncrypted: boolean }[]; comment: string | null }>('list_zip_contents', { zipPath: file.path });
       // Sort: Directories first
       entries.sort((a, b) => (a.isDir === b.isDir ? 0 : a.isDir ? -1 : 1) || a.name.localeCompare(b.name));
       setZipEntries(entries);
@@ -958,7 +1001,7 @@ export default function FileList({
 
     } catch (error) {
       console.error('Failed to list zip contents:', error);
-      setExtractError(String(error));
+      setExtractError(errorMessage(error));
       setIsZipEncrypted(true);
       setZipDialogOpen(true);
       setZipEntries([]);
@@ -967,7 +1010,7 @@ export default function FileList({
       // also show detailed modal
       setErrorDialogTitle('Failed to read ZIP contents');
       setErrorDialogMessage('An error occurred while reading the ZIP file content. It might be password protected. Please enter the password and try again.');
-      setErrorDialogDetails(String(error));
+      setErrorDialogDetails(errorMessage(error));
       setErrorDialogOpen(true);
     }
   };
@@ -1063,6 +1106,24 @@ export default function FileList({
     setSelectedZipEntries(newSelected);
   };
 
+  // 더블클릭한 항목을 임시 폴더에 풀어 연결된 프로그램으로 열기
+  const handleZipEntryOpen = async (entry: { name: string; isDir: boolean }) => {
+    if (!zipPath || entry.isDir) return;
+    setExtractError(null);
+    try {
+      await invoke('open_zip_entry', { zipPath, entryName: entry.name, password: extractPassword || null });
+    } catch (e) {
+      if (isPasswordError(e)) {
+        setExtractError(errorMessage(e));
+      } else {
+        setErrorDialogTitle('Open Failed');
+        setErrorDialogMessage('Could not open the entry. Please check the details below.');
+        setErrorDialogDetails(errorMessage(e));
+        setErrorDialogOpen(true);
+      }
+    }
+  };
+
   const handleZipDragStart = (e: React.DragEvent, entryName: string) => {
     if (!zipPath) return;
     const files = selectedZipEntries.has(entryName) ? Array.from(selectedZipEntries) : [entryName];
@@ -1148,17 +1209,13 @@ export default function FileList({
 
   const executeZipExtraction = async (zipPath: string, files: string[] | null, targetDir: string, password?: string) => {
     console.log(`[Frontend] Extracting zip. Password provided: "${password || ''}"`);
-    setExtractProgress({ total: 0, processed: 0, filename: 'Preparing...', startTime: Date.now() });
+    setExtractProgress({ total: 0, processed: 0, filename: 'Preparing...', bytesPerSec: 0, etaSecs: null });
+    setOperationPaused(false);
     let unlisten: UnlistenFn | undefined;
 
     try {
-      unlisten = await listen<{ total: number; processed: number; filename: string }>('extract-progress', (event) => {
-        setExtractProgress(prev => ({
-          total: event.payload.total,
-          processed: event.payload.processed,
-          filename: event.payload.filename,
-          startTime: prev ? prev.startTime : Date.now()
-        }));
+      unlisten = await listen<{ total: number; processed: number; filename: string; bytesPerSec: number; etaSecs: number | null }>('extract-progress', (event) => {
+        setExtractProgress(event.payload);
       });
 
       await invoke('extract_zip_files', {
@@ -1174,8 +1231,8 @@ export default function FileList({
       }
       return true;
     } catch (error) {
-      const errStr = String(error);
-      if (errStr === 'FILE_EXISTS') {
+      const errStr = errorMessage(error);
+      if (errorCode(error) === 'FILE_EXISTS') {
         const confirmed = await showConfirm('Confirm Overwrite', 'Some files already exist. Do you want to overwrite them?');
         if (confirmed) {
           try {
@@ -1192,9 +1249,9 @@ export default function FileList({
             }
             return true;
           } catch (e) {
-            const estr = String(e);
+            const estr = errorMessage(e);
             console.error('Extraction failed:', e);
-            if (estr.includes('Password required') || estr.includes('Invalid password')) {
+            if (isPasswordError(e)) {
               setExtractError(estr);
             } else {
               setErrorDialogTitle('Extraction Failed');
@@ -1207,7 +1264,7 @@ export default function FileList({
         return false;
       } else {
         console.error('Extraction failed:', error);
-        if (errStr.includes('Password required') || errStr.includes('Invalid password')) {
+        if (isPasswordError(error)) {
           setExtractError(errStr);
         } else {
           setErrorDialogTitle('Extraction Failed');
@@ -1263,8 +1320,8 @@ export default function FileList({
         setZipDialogOpen(false);
       }
     } catch (e) {
-      const err = String(e);
-      if (err.includes('Password required') || err.includes('Invalid password')) {
+      const err = errorMessage(e);
+      if (isPasswordError(e)) {
         setExtractError(err);
         // Keep ZIP dialog open so user can re-enter password
         setZipDialogOpen(true);
@@ -1288,8 +1345,8 @@ export default function FileList({
         setZipDialogOpen(false);
       }
     } catch (e) {
-      const err = String(e);
-      if (err.includes('Password required') || err.includes('Invalid password')) {
+      const err = errorMessage(e);
+      if (isPasswordError(e)) {
         setExtractError(err);
         setZipDialogOpen(true);
       } else {
@@ -1304,17 +1361,13 @@ export default function FileList({
   const handleExecuteCompress = async () => {
     if (!compressName || !compressTargetDir) return;
 
-    setCompressProgress({ total: 0, processed: 0, filename: 'Preparing...', startTime: Date.now() });
+    setCompressProgress({ total: 0, processed: 0, filename: 'Preparing...', bytesPerSec: 0, etaSecs: null });
+    setOperationPaused(false);
 
     let unlisten: UnlistenFn | undefined;
     try {
-      unlisten = await listen<{ total: number; processed: number; filename: string }>('compress-progress', (event) => {
-        setCompressProgress(prev => ({
-          total: event.payload.total,
-          processed: event.payload.processed,
-          filename: event.payload.filename,
-          startTime: prev ? prev.startTime : Date.now()
-        }));
+      unlisten = await listen<{ total: number; processed: number; filename: string; bytesPerSec: number; etaSecs: number | null }>('compress-progress', (event) => {
+        setCompressProgress(event.payload);
       });
 
       try {
@@ -1338,7 +1391,7 @@ export default function FileList({
         // Verify that the resulting ZIP reflects encryption when a password was provided.
         if (compressPassword) {
           try {
-            const entries = await invoke<{ name: string; isDir: boolean; size: number; isEncrypted: boolean }[]>('list_zip_contents', { zipPath: targetZipPath });
+            const { entries } = await invoke<{ entries: { name: string; isDir: boolean; size: number; isEncrypted: boolean }[]; comment: string | null }>('list_zip_contents', { zipPath: targetZipPath });
             const hasEncrypted = entries.some(e => e.isEncrypted);
             if (!hasEncrypted) {
               setErrorDialogTitle('Compression Password Warning');
@@ -1351,15 +1404,16 @@ export default function FileList({
             // Non-fatal: show details so user can inspect
             setErrorDialogTitle('Compression Verification Failed');
             setErrorDialogMessage('An error occurred while verifying the generated ZIP file content.');
-            setErrorDialogDetails(String(e));
+            setErrorDialogDetails(errorMessage(e));
             setErrorDialogOpen(true);
           }
         }
       } catch (error) {
+        if (errorCode(error) === 'CANCELLED') return;
         console.error('Compression failed:', error);
         setErrorDialogTitle('Compression Failed');
         setErrorDialogMessage('An error occurred during file compression. Please check the details below.');
-        setErrorDialogDetails(String(error));
+        setErrorDialogDetails(errorMessage(error));
         setErrorDialogOpen(true);
       } finally {
         if (unlisten) unlisten();
@@ -1891,7 +1945,7 @@ export default function FileList({
                   <span>{t('contextMenu.copyFileNames', 'Copy File Names')}</span>
                 </div>
               )}
-              {selectedFiles.size === 1 && Array.from(selectedFiles)[0].toLowerCase().endsWith('.zip') && (
+              {selectedFiles.size === 1 && (Array.from(selectedFiles)[0].toLowerCase().endsWith('.zip') || isTarArchive(Array.from(selectedFiles)[0])) && (
                 <div className={`context-menu-item ${!canExtractHere ? 'disabled' : ''}`} onClick={canExtractHere ? performExtract : undefined} style={{ padding: '2px 10px' }}>
                   <span>{t('contextMenu.extractHere')}</span> <span className="shortcut">Alt+E</span>
                 </div>
@@ -2085,6 +2139,7 @@ export default function FileList({
                 <li
                   key={entry.name}
                   onClick={(e) => handleZipEntryClick(e, entry.name)}
+                  onDoubleClick={() => handleZipEntryOpen(entry)}
                   draggable
                   onDragStart={(e) => handleZipDragStart(e, entry.name)}
                   style={{
@@ -2178,15 +2233,15 @@ export default function FileList({
                 </div>
                 <div style={{ fontSize: '0.8em', color: '#666', textAlign: 'right' }}>
                   {(() => {
-                    if (compressProgress.processed === 0 || compressProgress.total === 0) return 'Calculating...';
-                    const elapsed = (Date.now() - compressProgress.startTime) / 1000;
-                    const speed = compressProgress.processed / elapsed; // bytes per second
-                    const remainingBytes = compressProgress.total - compressProgress.processed;
-                    const remainingSeconds = remainingBytes / speed;
-                    if (!isFinite(remainingSeconds)) return 'Calculating...';
-                    return `Time remaining: approx ${Math.ceil(remainingSeconds)}s`;
+                    if (compressProgress.etaSecs === null || compressProgress.total === 0) return 'Calculating...';
+                    // 속도와 남은 시간은 백엔드가 평활화해서 보내줌
+                    return `${formatSize(compressProgress.bytesPerSec)}/s · Time remaining: approx ${compressProgress.etaSecs}s`;
                   })()}
                 </div>
+                <div style={{ display: 'flex', justifyContent: 'flex-end', gap: '8px' }}>
+                  <button onClick={toggleOperationPause}>{operationPaused ? 'Resume' : 'Pause'}</button>
+                  <button onClick={() => invoke('cancel_compression')}>Cancel</button>
+                </div>
               </div>
             ) : (
               <>
@@ -2233,7 +2288,11 @@ export default function FileList({
                     style={{ width: '100%', padding: '8px', boxSizing: 'border-box' }}
                   >
                     <option value="deflated">Deflate (Standard)</option>
+                    <option value="smart">Smart (Store Media and Archives)</option>
+                    <option value="adaptive">Adaptive (Store Incompressible Files)</option>
                     <option value="stored">Store (No Compression)</option>
+                    <option value="zstd">Zstandard (Faster, Less Compatible)</option>
+                    <option value="bzip2">BZip2</option>
                   </select>
                 </div>
 
@@ -2246,6 +2305,11 @@ export default function FileList({
                     style={{ width: '100%', padding: '8px', boxSizing: 'border-box' }}
                     placeholder="Enter password to encrypt files"
                   />
+                  {passwordStrength !== null && (
+                    <div style={{ fontSize: '0.75em', marginTop: '4px', color: ['#e11d48', '#ea580c', '#ca8a04', '#059669', '#047857'][passwordStrength] }}>
+                      Strength: {['Very Weak', 'Weak', 'Fair', 'Strong', 'Very Strong'][passwordStrength]}
+                    </div>
+                  )}
                 </div>
 
                 <div>
@@ -2257,6 +2321,7 @@ export default function FileList({
                     disabled={!compressPassword}
                   >
                   <option value="zipcrypto">ZipCrypto (Standard Windows Compatibility)</option>
+                  <option value="aes128">AES-128</option>
                   <option value="aes256">AES-256 (Enhanced Security)</option>
                   </select>
                   {compressPassword && (
@@ -2264,7 +2329,7 @@ export default function FileList({
                       {compressEncryption === 'zipcrypto' ? (
                         <span style={{ color: '#059669' }}>✔️ ZipCrypto is compatible with Windows standard Zip extractor.</span>
                       ) : (
-                        <span>⚠️ AES-256 is more secure but incompatible with Windows built-in extractor. (Use 7-Zip/BandiZip)</span>
+                        <span>⚠️ AES is more secure but incompatible with Windows built-in extractor. (Use 7-Zip/BandiZip)</span>
                       )}
                     </div>
                   )}
@@ -2312,15 +2377,14 @@ export default function FileList({
           </div>
           <div style={{ fontSize: '0.8em', color: '#666', textAlign: 'right' }}>
             {(() => {
-              if (extractProgress.processed === 0 || extractProgress.total === 0) return 'Calculating...';
-              const elapsed = (Date.now() - extractProgress.startTime) / 1000;
-              const speed = extractProgress.processed / elapsed;
-              const remainingBytes = extractProgress.total - extractProgress.processed;
-              const remainingSeconds = remainingBytes / speed;
-              if (!isFinite(remainingSeconds)) return 'Calculating...';
-              return `Time remaining: approx ${Math.ceil(remainingSeconds)}s`;
+              if (extractProgress.etaSecs === null || extractProgress.total === 0) return 'Calculating...';
+              // 속도와 남은 시간은 백엔드가 평활화해서 보내줌
+              return `${formatSize(extractProgress.bytesPerSec)}/s · Time remaining: approx ${extractProgress.etaSecs}s`;
             })()}
           </div>
+          <div style={{ display: 'flex', justifyContent: 'flex-end', marginTop: '8px' }}>
+            <button onClick={toggleOperationPause}>{operationPaused ? 'Resume' : 'Pause'}</button>
+          </div>
         </div>,
         document.body
       )}
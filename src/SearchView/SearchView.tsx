@@ -6,6 +6,7 @@ import { open } from '@tauri-apps/plugin-dialog';
 import { useTranslation } from 'react-i18next';
 import FileList from '../FileExplorer/FileList';
 import { SearchConfig } from '../App';
+import { errorMessage } from '../utils/commandError';
 
 interface SearchViewProps {
   searchQuery: string;
@@ -42,10 +43,20 @@ interface FileData {
   isDirectory: boolean;
 }
 
+interface IndexStatePayload {
+  drive: string;
+  state: 'not_built' | 'building' | 'ready' | 'stale';
+}
+
 interface FileChangePayload {
-  action: 'create' | 'delete';
+  action: 'create' | 'delete' | 'rename';
   path: string;
+  // rename일 때 이전 경로 (path는 새 경로)
+  old_path?: string;
   is_dir: boolean;
+  // USN 사유 비트와 그 이름 목록 (예: ['rename_new_name', 'close'])
+  reason: number;
+  reasons: string[];
 }
 
 function getOptimalSearchRoots(paths: string[]): string[] {
@@ -74,6 +85,9 @@ const parseSizeQuery = (input: string) => {
   return { operator, bytes };
 };
 
+// 인덱스 검색 한 페이지에 표시할 결과 수
+const PAGE_SIZE = 500;
+
 const FILE_TYPES = ['All', 'Folder', 'Image', 'Video', 'Audio', 'Archive', 'Document', 'Code'];
 
 export default function SearchView({
@@ -119,6 +133,10 @@ export default function SearchView({
   const [localQuery, setLocalQuery] = useState(searchConfig?.query || searchQuery);
   const [sizeQuery, setSizeQuery] = useState(searchConfig?.sizeQuery || '');
   const [useRegex, setUseRegex] = useState(searchConfig?.useRegex || false);
+  const [showSystem, setShowSystem] = useState(searchConfig?.showSystem || false);
+  // 인덱스 검색 결과 페이지 (PAGE_SIZE 단위)
+  const [page, setPage] = useState(0);
+  const [indexTotal, setIndexTotal] = useState(0);
   const [showFilters, setShowFilters] = useState(searchConfig?.showFilters || false);
   const [selectedType, setSelectedType] = useState(searchConfig?.type || 'All');
   const [dateAfter, setDateAfter] = useState(searchConfig?.dateAfter || '');
@@ -156,12 +174,13 @@ export default function SearchView({
         dateAfter,
         dateBefore,
         showFilters,
+        showSystem,
         searchMode,
         directorySearchPaths: Array.from(directorySearchPaths)
       });
     }, 1000);
     return () => clearTimeout(handler);
-  }, [localQuery, useRegex, sizeQuery, selectedType, dateAfter, dateBefore, showFilters, searchMode, directorySearchPaths, onSaveSearchConfig]);
+  }, [localQuery, useRegex, sizeQuery, selectedType, dateAfter, dateBefore, showFilters, showSystem, searchMode, directorySearchPaths, onSaveSearchConfig]);
 
   // Regex 유효성 검사 및 에러 상태 관리 (사이드 이펙트 분리)
   useEffect(() => {
@@ -206,7 +225,7 @@ export default function SearchView({
       setIsIndexReady(true); // 인덱싱 완료 후 준비 상태로 변경
     } catch (error) {
       console.error('Indexing failed:', error);
-      alert(`Indexing failed: ${String(error)}`);
+      alert(`Indexing failed: ${errorMessage(error)}`);
     } finally {
       setIsIndexing(false);
     }
@@ -225,6 +244,15 @@ export default function SearchView({
           setIsIndexReady(true);
         }
       });
+      // 화면이 늦게 열려 index-ready 이벤트를 놓쳤을 수 있으므로 현재 상태도 확인
+      try {
+        const states = await invoke<IndexStatePayload[]>('get_index_state');
+        if (states.some(s => s.state === 'ready' || s.state === 'stale')) {
+          setIsIndexReady(true);
+        }
+      } catch (error) {
+        console.warn('Failed to get index state:', error);
+      }
     }
 
     // 파일 변경 이벤트 리스너 설정
@@ -234,9 +262,11 @@ export default function SearchView({
         setResults(currentResults => {
           let newResults = [...currentResults];
           for (const change of event.payload) {
-            if (change.action === 'delete') {
-              newResults = newResults.filter(r => r.path !== change.path);
-            } else if (change.action === 'create') {
+            if (change.action === 'delete' || change.action === 'rename') {
+              const removedPath = change.old_path ?? change.path;
+              newResults = newResults.filter(r => r.path !== removedPath);
+            }
+            if (change.action === 'create' || change.action === 'rename') {
               // 현재 검색어와 일치하는 경우에만 추가
               const name = change.path.split(/[/\\]/).pop() || change.path;
               if (localQuery && name.toLowerCase().includes(localQuery.toLowerCase())) {
@@ -297,7 +327,9 @@ export default function SearchView({
       try {
         let paths: string[] = [];
         if (searchMode === 'index') {
-           paths = await invoke<string[]>('search_mft', { query: localQuery, useRegex });
+           const result = await invoke<{ paths: string[]; total: number }>('search_mft', { query: localQuery, useRegex, offset: page * PAGE_SIZE, limit: PAGE_SIZE, showSystem });
+           paths = result.paths;
+           setIndexTotal(result.total);
         } else {
            const searchRoots = getOptimalSearchRoots(Array.from(directorySearchPaths));
            const searchPromises = searchRoots.map(p => invoke<string[]>('search_directory', { path: p, query: localQuery, useRegex }));
@@ -308,7 +340,7 @@ export default function SearchView({
         if (!isMounted) return;
 
         // 검색 결과 제한 (성능 최적화)
-        const limitedPaths = paths.slice(0, 500);
+        const limitedPaths = paths.slice(0, PAGE_SIZE);
 
         // 경로 문자열을 FileData 객체로 변환
         // 주의: 모든 파일에 대해 stat을 호출하면 느려질 수 있으므로 필요한 경우에만 호출하거나 비동기로 처리
@@ -370,7 +402,12 @@ export default function SearchView({
       isMounted = false;
       clearTimeout(timeoutId);
     };
-  }, [localQuery, useRegex, refreshTrigger, searchMode, directorySearchPaths, isIndexReady, quickAccess, localRefresh]);
+  }, [localQuery, useRegex, showSystem, refreshTrigger, searchMode, directorySearchPaths, isIndexReady, quickAccess, localRefresh, page]);
+
+  // 검색 조건이 바뀌면 첫 페이지부터
+  useEffect(() => {
+    setPage(0);
+  }, [localQuery, useRegex, showSystem, searchMode]);
 
   // --- Client-Side Filtering Logic ---
   const filteredResults = useMemo(() => {
@@ -513,6 +550,17 @@ export default function SearchView({
               />
               <span style={{ fontSize: '0.75em', fontWeight: 'bold', color: '#475569' }}>REGEX</span>
             </label>
+            {searchMode === 'index' && (
+              <label title={t('search.showSystemFiles', 'Show system files')} style={{ display: 'flex', alignItems: 'center', gap: '4px', cursor: 'pointer', backgroundColor: '#f1f5f9', padding: '4px 8px', borderRadius: '4px' }}>
+                <input 
+                  type="checkbox" 
+                  checked={showSystem}
+                  onChange={(e) => setShowSystem(e.target.checked)}
+                  style={{ cursor: 'pointer' }}
+                />
+                <span style={{ fontSize: '0.75em', fontWeight: 'bold', color: '#475569' }}>SYS</span>
+              </label>
+            )}
             {localQuery && (
               <button onClick={() => setLocalQuery('')} style={{ background: 'none', border: 'none', cursor: 'pointer', color: '#94a3b8', fontSize: '1.1em' }}>✕</button>
             )}
@@ -683,6 +731,13 @@ export default function SearchView({
            {!isSearching && results.length !== filteredResults.length && (
              <span style={{ fontSize: '0.85em' }}>(Filtered: {results.length - filteredResults.length} excluded)</span>
            )}
+           {searchMode === 'index' && localQuery && indexTotal > PAGE_SIZE && (
+             <span style={{ display: 'flex', gap: '6px', alignItems: 'center' }}>
+               <button disabled={page === 0 || isSearching} onClick={() => setPage(p => p - 1)}>‹</button>
+               <span>{page * PAGE_SIZE + 1}-{Math.min((page + 1) * PAGE_SIZE, indexTotal)} / {indexTotal}</span>
+               <button disabled={(page + 1) * PAGE_SIZE >= indexTotal || isSearching} onClick={() => setPage(p => p + 1)}>›</button>
+             </span>
+           )}
         </div>
         <div style={{ flex: 1, minHeight: 0, opacity: isSearching ? 0.5 : 1, transition: 'opacity 0.2s' }}>
           <FileList
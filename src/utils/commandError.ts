@@ -0,0 +1,22 @@
+// Rust 명령어가 반환하는 오류 ({ code, message })
+export interface CommandError {
+  code: string;
+  message: string;
+}
+
+function isCommandError(e: unknown): e is CommandError {
+  return typeof e === 'object' && e !== null && 'code' in e && 'message' in e;
+}
+
+export function errorCode(e: unknown): string | null {
+  return isCommandError(e) ? e.code : null;
+}
+
+export function errorMessage(e: unknown): string {
+  return isCommandError(e) ? e.message : String(e);
+}
+
+export function isPasswordError(e: unknown): boolean {
+  const code = errorCode(e);
+  return code === 'PASSWORD_REQUIRED' || code === 'INVALID_PASSWORD';
+}